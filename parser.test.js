@@ -4,6 +4,9 @@ const { Release } = require("./lib/parser.js");
 test("parse snapshots", () => {
   // check against the rust snapshots
   fs.readdirSync("tests/snapshots").forEach((snap) => {
+    if (!snap.startsWith("test_serde__")) {
+      return;
+    }
     const match = fs
       .readFileSync(`tests/snapshots/${snap}`, "utf-8")
       .match(/expression: \"&release\((.*?)\)\"\n.*?---\n(.*)?$/s);
@@ -13,6 +16,7 @@ test("parse snapshots", () => {
     const parsedRelease = new Release(release);
     expect(parsedRelease.versionRaw).toEqual(output.version_raw);
     expect(parsedRelease.getBuildHash()).toEqual(output.build_hash);
+    expect(parsedRelease.getKind()).toEqual(output.kind);
     if (output.version_parsed) {
       const v = parsedRelease.versionParsed;
       expect(v.major).toEqual(output.version_parsed.major);
@@ -0,0 +1,119 @@
+use std::convert::TryFrom;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::parser::Release;
+
+/// The columns Sentry stores for a release.
+///
+/// This is the projection of a [`Release`] onto the `major`, `minor`,
+/// `patch`, `revision`, `prerelease`, `build_code`, `build_number` and
+/// `package` columns of Sentry's release table.  All numeric columns are
+/// signed 64bit integers:
+///
+/// - version components above `i64::MAX` are clamped to `i64::MAX`.
+/// - build codes that are numeric but do not fit into an `i64` do not set a
+///   `build_number`.  The value is retained in `build_code`.
+///
+/// If the release does not have a parsed version, all version related
+/// columns are `None`.  If it does, `prerelease` is an empty string for
+/// versions without a pre-release so that the column can be sorted on.
+///
+/// ```
+/// use sentry_release_parser::Release;
+///
+/// let release = Release::parse("org.example.FooApp@1.0rc1+20200101100").unwrap();
+/// let columns = release.columns();
+/// assert_eq!(columns.package(), Some("org.example.FooApp"));
+/// assert_eq!(columns.major(), Some(1));
+/// assert_eq!(columns.prerelease(), Some("rc1"));
+/// assert_eq!(columns.build_number(), Some(20200101100));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReleaseColumns<'a> {
+    major: Option<i64>,
+    minor: Option<i64>,
+    patch: Option<i64>,
+    revision: Option<i64>,
+    prerelease: Option<&'a str>,
+    build_code: Option<&'a str>,
+    build_number: Option<i64>,
+    package: Option<&'a str>,
+}
+
+fn clamp_component(raw: Option<&str>) -> i64 {
+    match raw {
+        // the version regex only permits digits here, so the only possible
+        // parse error is an overflow.
+        Some(raw) => raw.parse().unwrap_or(i64::MAX),
+        None => 0,
+    }
+}
+
+impl<'a> ReleaseColumns<'a> {
+    /// Projects a release onto the release columns.
+    pub fn new(release: &Release<'a>) -> ReleaseColumns<'a> {
+        let version = release.version();
+        let quad = version.map(|v| v.raw_quad());
+        ReleaseColumns {
+            major: quad.map(|q| clamp_component(Some(q.0))),
+            minor: quad.map(|q| clamp_component(q.1)),
+            patch: quad.map(|q| clamp_component(q.2)),
+            revision: quad.map(|q| clamp_component(q.3)),
+            prerelease: version.map(|v| v.pre().unwrap_or("")),
+            build_code: version.and_then(|v| v.build_code()),
+            build_number: version
+                .and_then(|v| v.build_number())
+                .and_then(|num| i64::try_from(num).ok()),
+            package: release.package(),
+        }
+    }
+
+    /// Returns the `major` column.
+    pub fn major(&self) -> Option<i64> {
+        self.major
+    }
+
+    /// Returns the `minor` column.
+    pub fn minor(&self) -> Option<i64> {
+        self.minor
+    }
+
+    /// Returns the `patch` column.
+    pub fn patch(&self) -> Option<i64> {
+        self.patch
+    }
+
+    /// Returns the `revision` column.
+    pub fn revision(&self) -> Option<i64> {
+        self.revision
+    }
+
+    /// Returns the `prerelease` column.
+    pub fn prerelease(&self) -> Option<&'a str> {
+        self.prerelease
+    }
+
+    /// Returns the `build_code` column.
+    pub fn build_code(&self) -> Option<&'a str> {
+        self.build_code
+    }
+
+    /// Returns the `build_number` column.
+    pub fn build_number(&self) -> Option<i64> {
+        self.build_number
+    }
+
+    /// Returns the `package` column.
+    pub fn package(&self) -> Option<&'a str> {
+        self.package
+    }
+}
+
+impl<'a> From<&Release<'a>> for ReleaseColumns<'a> {
+    fn from(release: &Release<'a>) -> ReleaseColumns<'a> {
+        ReleaseColumns::new(release)
+    }
+}
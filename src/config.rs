@@ -0,0 +1,157 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::glob::glob_match;
+
+/// An error indicating that a parser configuration refers to an unknown
+/// version scheme.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidParserConfig {
+    scheme: String,
+}
+
+impl InvalidParserConfig {
+    pub(crate) fn new(scheme: &str) -> InvalidParserConfig {
+        InvalidParserConfig {
+            scheme: scheme.to_string(),
+        }
+    }
+
+    /// Returns the name of the unknown scheme.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+}
+
+impl std::error::Error for InvalidParserConfig {}
+
+impl fmt::Display for InvalidParserConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid parser config: unknown scheme {:?}", self.scheme)
+    }
+}
+
+/// Matches the package of a release.
+///
+/// When deserialized this is written as `{"exact": "acme-api"}`,
+/// `{"prefix": "com.acme."}` or `{"glob": "@acme-*"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PackageMatcher {
+    /// Matches a package by name.
+    Exact(String),
+    /// Matches all packages starting with a prefix.
+    Prefix(String),
+    /// Matches packages against a pattern where `*` matches anything.
+    Glob(String),
+}
+
+impl PackageMatcher {
+    /// Checks if the package matches.
+    pub fn matches(&self, package: &str) -> bool {
+        match self {
+            PackageMatcher::Exact(name) => package == name,
+            PackageMatcher::Prefix(prefix) => package.starts_with(prefix.as_str()),
+            PackageMatcher::Glob(pattern) => glob_match(pattern, package),
+        }
+    }
+}
+
+/// Assigns a version scheme to the packages matched by a matcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct SchemeRule {
+    #[cfg_attr(feature = "serde", serde(rename = "match"))]
+    matcher: PackageMatcher,
+    scheme: String,
+}
+
+impl SchemeRule {
+    /// Creates a rule using the named scheme for the matched packages.
+    pub fn new(matcher: PackageMatcher, scheme: &str) -> SchemeRule {
+        SchemeRule {
+            matcher,
+            scheme: scheme.to_string(),
+        }
+    }
+
+    /// Returns the package matcher.
+    pub fn matcher(&self) -> &PackageMatcher {
+        &self.matcher
+    }
+
+    /// Returns the name of the scheme.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+}
+
+/// Configures which version scheme a [`Parser`](crate::Parser) uses per
+/// package.
+///
+/// Rules are checked in order and the first rule matching the package of a
+/// release decides the scheme.  Packages without a matching rule use the
+/// schemes registered on the parser and then the fallback scheme.  The
+/// built-in schemes are `default`, `semver` and `pep440`.
+///
+/// With the `serde` feature the configuration can be deserialized:
+///
+/// ```json
+/// {
+///   "rules": [
+///     {"match": {"prefix": "com.acme."}, "scheme": "default"},
+///     {"match": {"exact": "acme-api"}, "scheme": "pep440"},
+///     {"match": {"glob": "@acme-*"}, "scheme": "semver"}
+///   ],
+///   "fallback": "default"
+/// }
+/// ```
+///
+/// ```
+/// use sentry_release_parser::{PackageMatcher, Parser, ParserConfig, Release};
+///
+/// let config = ParserConfig::new()
+///     .with_rule(PackageMatcher::Exact("acme-api".into()), "pep440");
+/// let parser = Parser::from_config(&config).unwrap();
+/// let release = Release::parse_with("acme-api@1.0.post1", &parser).unwrap();
+/// assert_eq!(release.scheme(), Some("pep440"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ParserConfig {
+    rules: Vec<SchemeRule>,
+    fallback: Option<String>,
+}
+
+impl ParserConfig {
+    /// Creates an empty configuration.
+    pub fn new() -> ParserConfig {
+        ParserConfig::default()
+    }
+
+    /// Adds a rule using the named scheme for the matched packages.
+    pub fn with_rule(mut self, matcher: PackageMatcher, scheme: &str) -> ParserConfig {
+        self.rules.push(SchemeRule::new(matcher, scheme));
+        self
+    }
+
+    /// Sets the scheme for packages without a matching rule.
+    pub fn with_fallback(mut self, scheme: &str) -> ParserConfig {
+        self.fallback = Some(scheme.to_string());
+        self
+    }
+
+    /// Returns the rules.
+    pub fn rules(&self) -> &[SchemeRule] {
+        &self.rules
+    }
+
+    /// Returns the name of the fallback scheme if configured.
+    pub fn fallback(&self) -> Option<&str> {
+        self.fallback.as_deref()
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(feature = "semver-1")]
+use std::{convert::TryFrom, fmt};
+
+#[cfg(any(feature = "semver", feature = "semver-1"))]
+use crate::parser::OwnedVersion;
+#[cfg(feature = "semver-1")]
+use crate::parser::Version;
+
+/// Reports which parts of a version a semver conversion loses.
+///
+/// Semver versions do not have a revision component, so converting a
+/// version with a non zero revision loses it.  Pre-releases and build codes
+/// are lost if they are not valid under the stricter semver rules.
+///
+/// Requires the `semver-1` feature.
+#[cfg(feature = "semver-1")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Conversion {
+    revision: bool,
+    pre: bool,
+    build_code: bool,
+}
+
+#[cfg(feature = "semver-1")]
+impl Conversion {
+    pub(crate) fn new(revision: bool, pre: bool, build_code: bool) -> Conversion {
+        Conversion {
+            revision,
+            pre,
+            build_code,
+        }
+    }
+
+    /// Returns `true` if nothing is lost.
+    pub fn is_lossless(&self) -> bool {
+        !(self.revision || self.pre || self.build_code)
+    }
+
+    /// Returns `true` if the revision component is lost.
+    pub fn loses_revision(&self) -> bool {
+        self.revision
+    }
+
+    /// Returns `true` if the pre-release identifier is lost.
+    pub fn loses_pre(&self) -> bool {
+        self.pre
+    }
+
+    /// Returns `true` if the build code is lost.
+    pub fn loses_build_code(&self) -> bool {
+        self.build_code
+    }
+}
+
+#[cfg(feature = "semver-1")]
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lost: Vec<_> = [
+            (self.revision, "revision"),
+            (self.pre, "pre-release"),
+            (self.build_code, "build code"),
+        ]
+        .iter()
+        .filter(|x| x.0)
+        .map(|x| x.1)
+        .collect();
+        if lost.is_empty() {
+            write!(f, "lossless")
+        } else {
+            write!(f, "loses {}", lost.join(", "))
+        }
+    }
+}
+
+/// An error indicating that a conversion would lose information.
+///
+/// Requires the `semver-1` feature.
+#[cfg(feature = "semver-1")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LossyConversion(Conversion);
+
+#[cfg(feature = "semver-1")]
+impl LossyConversion {
+    pub(crate) fn new(conversion: Conversion) -> LossyConversion {
+        LossyConversion(conversion)
+    }
+
+    /// Returns the report of what would be lost.
+    pub fn conversion(&self) -> Conversion {
+        self.0
+    }
+}
+
+#[cfg(feature = "semver-1")]
+impl std::error::Error for LossyConversion {}
+
+#[cfg(feature = "semver-1")]
+impl fmt::Display for LossyConversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "lossy semver conversion: {}", self.0)
+    }
+}
+
+/// Converts a version into a semver (1.0 API) without losing information.
+#[cfg(feature = "semver-1")]
+impl<'a> TryFrom<&Version<'a>> for semver_1::Version {
+    type Error = LossyConversion;
+
+    fn try_from(version: &Version<'a>) -> Result<semver_1::Version, LossyConversion> {
+        let conversion = version.semver1_conversion();
+        if conversion.is_lossless() {
+            Ok(version.as_semver1())
+        } else {
+            Err(LossyConversion::new(conversion))
+        }
+    }
+}
+
+#[cfg(feature = "semver-1")]
+impl From<&semver_1::Version> for OwnedVersion {
+    fn from(version: &semver_1::Version) -> OwnedVersion {
+        // the semver grammar is a subset of what versions accept
+        OwnedVersion::parse(version.to_string()).expect("semver is always a valid version")
+    }
+}
+
+/// Converts a semver (0.9 API) version.
+///
+/// The identifiers of these versions are not validated, so pre-release
+/// identifiers and build metadata that cannot be represented are dropped.
+#[cfg(feature = "semver")]
+impl From<&semver::Version> for OwnedVersion {
+    fn from(version: &semver::Version) -> OwnedVersion {
+        fn join(identifiers: &[semver::Identifier]) -> String {
+            identifiers
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        }
+
+        let base = format!("{}.{}.{}", version.major, version.minor, version.patch);
+        let pre = join(&version.pre);
+        let build = join(&version.build);
+        let pre = if pre.is_empty() {
+            String::new()
+        } else {
+            format!("-{}", pre)
+        };
+        let build = if build.is_empty() {
+            String::new()
+        } else {
+            format!("+{}", build)
+        };
+
+        [
+            format!("{}{}{}", base, pre, build),
+            format!("{}{}", base, pre),
+            base,
+        ]
+        .iter()
+        .find_map(|raw| OwnedVersion::parse(raw.clone()).ok())
+        .expect("plain version triple is always valid")
+    }
+}
@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::parser::{Release, Version};
+
+/// Classifies the change between two versions.
+///
+/// See [`Version::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "snake_case"))]
+pub enum VersionChange {
+    /// The versions are identical.
+    Unchanged,
+    /// The major version component was increased.
+    Major,
+    /// The minor version component was increased.
+    Minor,
+    /// The patch level version component was increased.
+    Patch,
+    /// The revision level version component was increased.
+    Revision,
+    /// Only the pre-release identifier changed.
+    Prerelease,
+    /// Only the build code changed.
+    Build,
+    /// The new version is lower than the old version.
+    ///
+    /// Build codes only count as lower if both are build numbers.
+    Downgrade,
+}
+
+impl VersionChange {
+    pub(crate) fn between(old: &Version<'_>, new: &Version<'_>) -> VersionChange {
+        // build codes are only ordered if both are build numbers
+        let downgrade = match new.cmp_precedence(old) {
+            Ordering::Equal => matches!(
+                (new.build_number(), old.build_number()),
+                (Some(new_build), Some(old_build)) if new_build < old_build
+            ),
+            ordering => ordering == Ordering::Less,
+        };
+        if downgrade {
+            return VersionChange::Downgrade;
+        }
+
+        let (old_major, old_minor, old_patch, old_revision) = old.quad();
+        let (new_major, new_minor, new_patch, new_revision) = new.quad();
+        if old_major != new_major {
+            VersionChange::Major
+        } else if old_minor != new_minor {
+            VersionChange::Minor
+        } else if old_patch != new_patch {
+            VersionChange::Patch
+        } else if old_revision != new_revision {
+            VersionChange::Revision
+        } else if old.pre() != new.pre() {
+            VersionChange::Prerelease
+        } else if old.build_code() != new.build_code() {
+            VersionChange::Build
+        } else {
+            VersionChange::Unchanged
+        }
+    }
+}
+
+/// Describes the change between two releases.
+///
+/// See [`Release::diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ReleaseChange {
+    package_changed: bool,
+    version_change: Option<VersionChange>,
+    hash_changed: bool,
+}
+
+impl ReleaseChange {
+    pub(crate) fn between(old: &Release<'_>, new: &Release<'_>) -> ReleaseChange {
+        ReleaseChange {
+            package_changed: old.package() != new.package(),
+            version_change: match (old.version(), new.version()) {
+                (Some(old_ver), Some(new_ver)) => Some(old_ver.diff(new_ver)),
+                _ => None,
+            },
+            hash_changed: old.build_hash() != new.build_hash(),
+        }
+    }
+
+    /// Returns `true` if the package changed.
+    pub fn package_changed(&self) -> bool {
+        self.package_changed
+    }
+
+    /// Returns the change of the version.
+    ///
+    /// This is `None` unless both releases have a parsed version.
+    pub fn version_change(&self) -> Option<VersionChange> {
+        self.version_change
+    }
+
+    /// Returns `true` if the build hash changed.
+    pub fn hash_changed(&self) -> bool {
+        self.hash_changed
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::parser::{validate_environment, InvalidEnvironment};
+
+/// The aliases recognized by default.
+const DEFAULT_ALIASES: [(&str, EnvironmentTier); 29] = [
+    ("production", EnvironmentTier::Production),
+    ("prod", EnvironmentTier::Production),
+    ("prd", EnvironmentTier::Production),
+    ("live", EnvironmentTier::Production),
+    ("release", EnvironmentTier::Production),
+    ("staging", EnvironmentTier::Staging),
+    ("stage", EnvironmentTier::Staging),
+    ("stg", EnvironmentTier::Staging),
+    ("preprod", EnvironmentTier::Staging),
+    ("pre-prod", EnvironmentTier::Staging),
+    ("uat", EnvironmentTier::Staging),
+    ("development", EnvironmentTier::Development),
+    ("develop", EnvironmentTier::Development),
+    ("dev", EnvironmentTier::Development),
+    ("local", EnvironmentTier::Development),
+    ("localhost", EnvironmentTier::Development),
+    ("debug", EnvironmentTier::Development),
+    ("testing", EnvironmentTier::Testing),
+    ("test", EnvironmentTier::Testing),
+    ("tst", EnvironmentTier::Testing),
+    ("qa", EnvironmentTier::Testing),
+    ("ci", EnvironmentTier::Testing),
+    ("integration", EnvironmentTier::Testing),
+    ("sandbox", EnvironmentTier::Testing),
+    ("preview", EnvironmentTier::Preview),
+    ("review", EnvironmentTier::Preview),
+    ("pr", EnvironmentTier::Preview),
+    ("canary", EnvironmentTier::Preview),
+    ("beta", EnvironmentTier::Preview),
+];
+
+/// The separators between the tier and the qualifier of an environment.
+const SEPARATORS: [char; 4] = ['-', '_', '.', ':'];
+
+/// The tier of a deployment environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EnvironmentTier {
+    /// Production environments serving real users.
+    Production,
+    /// Staging and pre-production environments.
+    Staging,
+    /// Development and local environments.
+    Development,
+    /// Test, QA and CI environments.
+    Testing,
+    /// Preview, review and canary deployments.
+    Preview,
+    /// The environment does not match any alias.
+    Unknown,
+}
+
+impl EnvironmentTier {
+    /// Returns the name of the tier.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            EnvironmentTier::Production => "production",
+            EnvironmentTier::Staging => "staging",
+            EnvironmentTier::Development => "development",
+            EnvironmentTier::Testing => "testing",
+            EnvironmentTier::Preview => "preview",
+            EnvironmentTier::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for EnvironmentTier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Maps environment names to tiers.
+///
+/// Aliases are matched case-insensitively.  The default aliases cover the
+/// common names such as `prod`, `production`, `prd` and `live` for
+/// production or `stg` and `uat` for staging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TierAliases {
+    aliases: Vec<(String, EnvironmentTier)>,
+}
+
+impl Default for TierAliases {
+    fn default() -> TierAliases {
+        TierAliases {
+            aliases: DEFAULT_ALIASES
+                .iter()
+                .map(|&(alias, tier)| (alias.to_string(), tier))
+                .collect(),
+        }
+    }
+}
+
+impl TierAliases {
+    /// Creates the default aliases.
+    pub fn new() -> TierAliases {
+        TierAliases::default()
+    }
+
+    /// Creates an alias list without any aliases.
+    pub fn empty() -> TierAliases {
+        TierAliases { aliases: vec![] }
+    }
+
+    /// Adds an alias for a tier.
+    ///
+    /// Aliases added later take precedence over existing ones.
+    pub fn with_alias(mut self, alias: &str, tier: EnvironmentTier) -> TierAliases {
+        self.aliases.insert(0, (alias.to_lowercase(), tier));
+        self
+    }
+
+    /// Iterates over the aliases and their tiers.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, EnvironmentTier)> {
+        self.aliases
+            .iter()
+            .map(|(alias, tier)| (alias.as_str(), *tier))
+    }
+
+    /// Looks up the tier of a name.
+    pub fn tier(&self, name: &str) -> Option<EnvironmentTier> {
+        self.aliases()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|x| x.1)
+    }
+
+    /// Classifies a (trimmed) environment name into tier and qualifier.
+    ///
+    /// The entire name is looked up first.  Otherwise the longest prefix
+    /// before a separator that is an alias wins (`prod-us-east-1`), followed
+    /// by the longest suffix after a separator (`us-east-1-prod`).
+    fn classify<'a>(&self, name: &'a str) -> (EnvironmentTier, Option<&'a str>) {
+        if let Some(tier) = self.tier(name) {
+            return (tier, None);
+        }
+        let positions = name.match_indices(&SEPARATORS[..]).map(|x| x.0);
+        for idx in positions.clone().rev() {
+            if let Some(tier) = self.tier(&name[..idx]) {
+                return (tier, Some(&name[idx + 1..]).filter(|x| !x.is_empty()));
+            }
+        }
+        for idx in positions {
+            if let Some(tier) = self.tier(&name[idx + 1..]) {
+                return (tier, Some(&name[..idx]).filter(|x| !x.is_empty()));
+            }
+        }
+        (EnvironmentTier::Unknown, None)
+    }
+}
+
+/// A validated deployment environment.
+///
+/// ```
+/// use sentry_release_parser::{Environment, EnvironmentTier};
+///
+/// let environment = Environment::parse("Prod-us-east-1").unwrap();
+/// assert_eq!(environment.normalized(), "prod-us-east-1");
+/// assert_eq!(environment.tier(), EnvironmentTier::Production);
+/// assert_eq!(environment.qualifier(), Some("us-east-1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Environment<'a> {
+    raw: &'a str,
+    trimmed: &'a str,
+    tier: EnvironmentTier,
+    qualifier: Option<&'a str>,
+}
+
+impl<'a> Environment<'a> {
+    /// Parses an environment with the default tier aliases.
+    ///
+    /// This validates the environment like [`validate_environment`].
+    pub fn parse(environment: &'a str) -> Result<Environment<'a>, InvalidEnvironment> {
+        Environment::parse_with(environment, &TierAliases::default())
+    }
+
+    /// Parses an environment with custom tier aliases.
+    ///
+    /// Surrounding whitespace is ignored, so the trimmed environment is
+    /// what has to be valid.
+    pub fn parse_with(
+        environment: &'a str,
+        aliases: &TierAliases,
+    ) -> Result<Environment<'a>, InvalidEnvironment> {
+        let trimmed = environment.trim();
+        let start = trimmed.as_ptr() as usize - environment.as_ptr() as usize;
+        validate_environment(trimmed).map_err(|err| match err {
+            InvalidEnvironment::BadCharacters { character, offset } => {
+                InvalidEnvironment::BadCharacters {
+                    character,
+                    offset: start + offset,
+                }
+            }
+            err => err,
+        })?;
+        let (tier, qualifier) = aliases.classify(trimmed);
+        Ok(Environment {
+            raw: environment,
+            trimmed,
+            tier,
+            qualifier,
+        })
+    }
+
+    /// Returns the environment as it was passed in.
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    /// Returns the trimmed and lowercased environment.
+    pub fn normalized(&self) -> String {
+        self.trimmed.to_lowercase()
+    }
+
+    /// Returns the tier of the environment.
+    pub fn tier(&self) -> EnvironmentTier {
+        self.tier
+    }
+
+    /// Returns the qualifier next to the tier alias.
+    ///
+    /// This is usually a region or cluster name such as `us-east-1` in
+    /// `prod-us-east-1`.
+    pub fn qualifier(&self) -> Option<&'a str> {
+        self.qualifier
+    }
+
+    /// Returns `true` if the environment is a production environment.
+    pub fn is_production(&self) -> bool {
+        self.tier == EnvironmentTier::Production
+    }
+}
+
+impl<'a> fmt::Display for Environment<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
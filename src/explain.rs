@@ -0,0 +1,314 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::parser::{
+    validate_release, InvalidRelease, Release, ReleaseKind, Version, BUILD_HASH_LENGTHS,
+};
+
+lazy_static! {
+    // these mirror the parts of the version regex so that we can tell which
+    // part of a version is at fault.
+    pub(crate) static ref COMPONENTS_REGEX: Regex = Regex::new(r"^[0-9]+(?:\.[0-9]+)*").unwrap();
+    pub(crate) static ref PRERELEASE_REGEX: Regex = Regex::new(
+        r"(?x)
+        ^
+            (?:-|[a-z])
+            (?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*)?
+            (?:\.(?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*))*
+        $
+        "
+    )
+    .unwrap();
+    pub(crate) static ref BUILD_CODE_REGEX: Regex =
+        Regex::new(r"^[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*$").unwrap();
+}
+
+/// The rule that made a version fail to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionFailure {
+    /// The version is empty.
+    Empty,
+    /// The version does not start with a number.
+    NoLeadingNumber,
+    /// The version has more than four numeric components.
+    TooManyComponents,
+    /// The pre-release part is not valid.
+    InvalidPrerelease,
+    /// The build code after the `+` is not valid.
+    InvalidBuildCode,
+    /// A version with a single component has a pre-release that does not
+    /// start with a dash (`1a1` instead of `1-a1`).
+    SingleComponentPrerelease,
+}
+
+impl VersionFailure {
+    fn diagnose(version: &str) -> VersionFailure {
+        if version.is_empty() {
+            return VersionFailure::Empty;
+        }
+        let components = match COMPONENTS_REGEX.find(version) {
+            Some(m) => m,
+            None => return VersionFailure::NoLeadingNumber,
+        };
+        if components.as_str().split('.').count() > 4 {
+            return VersionFailure::TooManyComponents;
+        }
+        let rest = &version[components.end()..];
+        let (pre, build_code) = match rest.find('+') {
+            Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+            None => (rest, None),
+        };
+        if build_code.is_some_and(|code| !BUILD_CODE_REGEX.is_match(code)) {
+            VersionFailure::InvalidBuildCode
+        } else if !pre.is_empty() && !PRERELEASE_REGEX.is_match(pre) {
+            VersionFailure::InvalidPrerelease
+        } else if !components.as_str().contains('.') && !pre.is_empty() && !pre.starts_with('-') {
+            VersionFailure::SingleComponentPrerelease
+        } else {
+            VersionFailure::InvalidPrerelease
+        }
+    }
+}
+
+impl fmt::Display for VersionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                VersionFailure::Empty => "version is empty",
+                VersionFailure::NoLeadingNumber => "version does not start with a number",
+                VersionFailure::TooManyComponents => "more than four numeric components",
+                VersionFailure::InvalidPrerelease => "invalid pre-release",
+                VersionFailure::InvalidBuildCode => "invalid build code",
+                VersionFailure::SingleComponentPrerelease => {
+                    "pre-release of a single component version must start with a dash"
+                }
+            }
+        )
+    }
+}
+
+/// The result of checking whether a value is a build hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashCheck {
+    length: usize,
+    hash_length: bool,
+    hex: bool,
+}
+
+impl HashCheck {
+    fn new(value: &str) -> HashCheck {
+        HashCheck {
+            length: value.len(),
+            hash_length: BUILD_HASH_LENGTHS.contains(&value.len()),
+            hex: !value.is_empty() && value.bytes().all(|c| c.is_ascii_hexdigit()),
+        }
+    }
+
+    /// Returns the length of the checked value.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the length is one of the build hash lengths.
+    pub fn has_hash_length(&self) -> bool {
+        self.hash_length
+    }
+
+    /// Returns `true` if the value only contains hexadecimal digits.
+    pub fn is_hex(&self) -> bool {
+        self.hex
+    }
+
+    /// Returns `true` if the value is considered a build hash.
+    pub fn is_hash(&self) -> bool {
+        self.hash_length && self.hex
+    }
+}
+
+impl fmt::Display for HashCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_hash() {
+            write!(f, "build hash ({} hexadecimal characters)", self.length)
+        } else if !self.hex {
+            write!(f, "not a build hash (not hexadecimal)")
+        } else {
+            write!(
+                f,
+                "not a build hash (length {} is not one of {})",
+                self.length,
+                BUILD_HASH_LENGTHS
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+/// A trace of how a release string is parsed.
+///
+/// This is returned by [`Release::explain`] and records every decision
+/// [`Release::parse`] makes: the whitespace that was trimmed, the result of
+/// validation, whether the input was split into package and version, the
+/// build hash check on the version part, why the version did or did not
+/// parse and the resulting [`ReleaseKind`].
+///
+/// ```
+/// use sentry_release_parser::{Release, VersionFailure};
+///
+/// let explanation = Release::explain("myapp@1.0.0.0.1");
+/// assert_eq!(explanation.package(), Some("myapp"));
+/// assert_eq!(explanation.version_failure(), Some(VersionFailure::TooManyComponents));
+/// println!("{}", explanation);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReleaseExplanation<'a> {
+    input: &'a str,
+    trimmed: &'a str,
+    validation: Result<(), InvalidRelease>,
+    hash_check: Option<HashCheck>,
+    version: Option<Result<Version<'a>, VersionFailure>>,
+    release: Option<Release<'a>>,
+}
+
+impl<'a> ReleaseExplanation<'a> {
+    pub(crate) fn new(input: &'a str) -> ReleaseExplanation<'a> {
+        let trimmed = input.trim();
+        let mut rv = ReleaseExplanation {
+            input,
+            trimmed,
+            validation: validate_release(trimmed),
+            hash_check: None,
+            version: None,
+            release: None,
+        };
+        if rv.validation.is_err() {
+            return rv;
+        }
+
+        let release = Release::parse(trimmed).expect("validated release must parse");
+        let hash_check = HashCheck::new(release.version_raw());
+        if release.package().is_some() && !hash_check.is_hash() {
+            rv.version = Some(
+                Version::parse(release.version_raw())
+                    .map_err(|_| VersionFailure::diagnose(release.version_raw())),
+            );
+        }
+        rv.hash_check = Some(hash_check);
+        rv.release = Some(release);
+        rv
+    }
+
+    /// Returns the release as it was passed in.
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    /// Returns the release after trimming whitespace.
+    pub fn trimmed(&self) -> &'a str {
+        self.trimmed
+    }
+
+    /// Returns `true` if whitespace was trimmed.
+    pub fn was_trimmed(&self) -> bool {
+        self.input.len() != self.trimmed.len()
+    }
+
+    /// Returns the validation error if the release is invalid.
+    pub fn validation_error(&self) -> Option<&InvalidRelease> {
+        self.validation.as_ref().err()
+    }
+
+    /// Returns the package if the release was split at an `@`.
+    pub fn package(&self) -> Option<&'a str> {
+        self.release.as_ref().and_then(|r| r.package())
+    }
+
+    /// Returns the version part the build hash check was applied to.
+    ///
+    /// Without a package this is the entire release.
+    pub fn version_raw(&self) -> Option<&'a str> {
+        self.release.as_ref().map(|r| r.version_raw())
+    }
+
+    /// Returns the result of the build hash check on the version part.
+    pub fn hash_check(&self) -> Option<HashCheck> {
+        self.hash_check
+    }
+
+    /// Returns `true` if parsing the version was attempted.
+    ///
+    /// Versions are only parsed if the release has a package and the
+    /// version part is not a build hash.
+    pub fn version_attempted(&self) -> bool {
+        self.version.is_some()
+    }
+
+    /// Returns the parsed version.
+    pub fn version(&self) -> Option<&Version<'a>> {
+        self.version.as_ref().and_then(|v| v.as_ref().ok())
+    }
+
+    /// Returns the rule that made the version fail to parse.
+    pub fn version_failure(&self) -> Option<VersionFailure> {
+        self.version
+            .as_ref()
+            .and_then(|v| v.as_ref().err().copied())
+    }
+
+    /// Returns the parsed release if it was valid.
+    pub fn release(&self) -> Option<&Release<'a>> {
+        self.release.as_ref()
+    }
+
+    /// Returns the kind the release was classified as.
+    pub fn kind(&self) -> Option<ReleaseKind> {
+        self.release.as_ref().map(|r| r.kind())
+    }
+}
+
+impl<'a> fmt::Display for ReleaseExplanation<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "input: {:?}", self.input)?;
+        if self.was_trimmed() {
+            writeln!(f, "trimmed: {:?}", self.trimmed)?;
+        } else {
+            writeln!(f, "trimmed: nothing to trim")?;
+        }
+        if let Err(ref err) = self.validation {
+            return write!(f, "validation: {}", err);
+        }
+        writeln!(f, "validation: ok")?;
+
+        match self.package() {
+            Some(package) => writeln!(
+                f,
+                "split: package {:?}, version {:?}",
+                package,
+                self.version_raw().unwrap_or_default()
+            )?,
+            None => writeln!(f, "split: no package (no @ separator)")?,
+        }
+        if let Some(hash_check) = self.hash_check {
+            writeln!(f, "hash: {}", hash_check)?;
+        }
+        match self.version {
+            Some(Ok(ref version)) => writeln!(f, "version: parsed {}", version)?,
+            Some(Err(failure)) => writeln!(f, "version: failed ({})", failure)?,
+            None if self.package().is_none() => {
+                writeln!(f, "version: not parsed (releases need a package)")?
+            }
+            None => writeln!(f, "version: not parsed (build hash)")?,
+        }
+
+        if let Some(ref release) = self.release {
+            write!(f, "kind: {}", release.kind())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+
+use crate::parser::{OwnedVersion, Release};
+
+/// The flavor suffixes detected by default.
+const DEFAULT_SUFFIXES: [&str; 4] = ["debug", "staging", "internal", "qa"];
+
+/// Configures which suffixes are detected as build flavors.
+///
+/// By default `debug`, `staging`, `internal` and `qa` are detected.
+/// Suffixes are matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlavorConfig {
+    suffixes: Vec<String>,
+}
+
+impl Default for FlavorConfig {
+    fn default() -> FlavorConfig {
+        FlavorConfig {
+            suffixes: DEFAULT_SUFFIXES.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+
+impl FlavorConfig {
+    /// Creates a configuration detecting the default suffixes.
+    pub fn new() -> FlavorConfig {
+        FlavorConfig::default()
+    }
+
+    /// Creates a configuration that does not detect any suffixes.
+    pub fn empty() -> FlavorConfig {
+        FlavorConfig { suffixes: vec![] }
+    }
+
+    /// Adds a suffix to detect.
+    pub fn with_suffix(mut self, suffix: &str) -> FlavorConfig {
+        self.suffixes.push(suffix.to_string());
+        self
+    }
+
+    /// Returns the suffixes that are detected.
+    pub fn suffixes(&self) -> impl Iterator<Item = &str> {
+        self.suffixes.iter().map(|x| x.as_str())
+    }
+
+    /// Splits a flavor suffix off a value.
+    ///
+    /// The flavor has to follow one of the given separators or make up the
+    /// entire value.  Returns the base and the flavor.
+    fn split<'a>(&self, value: &'a str, separators: &[char]) -> Option<(&'a str, &'a str)> {
+        self.suffixes().find_map(|suffix| {
+            let idx = value.len().checked_sub(suffix.len())?;
+            let flavor = value.get(idx..)?;
+            if !flavor.eq_ignore_ascii_case(suffix) {
+                return None;
+            }
+            let base = &value[..idx];
+            match base.chars().last() {
+                None => Some((base, flavor)),
+                Some(c) if separators.contains(&c) => Some((&base[..base.len() - 1], flavor)),
+                Some(_) => None,
+            }
+        })
+    }
+
+    /// Detects the flavor of a release.
+    ///
+    /// The package is checked first for a suffix after a `.`, `-` or `_`
+    /// (`com.acme.app.debug`).  Otherwise the pre-release of the version is
+    /// checked for a suffix that is the entire pre-release or follows a `.`
+    /// or `-` (`1.2.3-debug`, `1.2.3-rc1.debug`).
+    pub fn detect<'a>(&self, release: &Release<'a>) -> FlavoredRelease<'a> {
+        let mut rv = FlavoredRelease {
+            release: release.clone(),
+            base_package: release.package(),
+            base_version: release.version().map(OwnedVersion::from),
+            flavor: None,
+            location: None,
+        };
+
+        if let Some((base, flavor)) = release
+            .package()
+            .and_then(|package| self.split(package, &['.', '-', '_']))
+            .filter(|(base, _)| !base.is_empty())
+        {
+            rv.base_package = Some(base);
+            rv.flavor = Some(flavor);
+            rv.location = Some(FlavorLocation::Package);
+            return rv;
+        }
+
+        let version = match release.version() {
+            Some(version) => version,
+            None => return rv,
+        };
+        let (flavor, base_version) = match version.pre().and_then(|pre| {
+            let (_, flavor) = self.split(pre, &['.', '-'])?;
+            let short = version.raw_short();
+            let base = short[..short.len() - flavor.len()].trim_end_matches(&['.', '-'][..]);
+            let base = match version.build_code() {
+                Some(build_code) => format!("{}+{}", base, build_code),
+                None => base.to_string(),
+            };
+            Some((flavor, OwnedVersion::parse(base).ok()?))
+        }) {
+            Some(rv) => rv,
+            None => return rv,
+        };
+        rv.base_version = Some(base_version);
+        rv.flavor = Some(flavor);
+        rv.location = Some(FlavorLocation::Version);
+        rv
+    }
+}
+
+/// Where the flavor of a release was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlavorLocation {
+    /// The flavor is a suffix of the package.
+    Package,
+    /// The flavor is (part of) the pre-release of the version.
+    Version,
+}
+
+/// A release with its build flavor separated out.
+///
+/// Mobile apps often report build variants as part of the release
+/// (`com.acme.app.debug@1.2.3` or `com.acme.app@1.2.3-debug`).  As
+/// pre-release the flavor makes the version sort before the final release.
+/// This separates the flavor from the base package and version so that
+/// releases can be compared independent of their flavor.
+///
+/// ```
+/// use sentry_release_parser::Release;
+///
+/// let release = Release::parse("com.acme.app@1.2.3-debug").unwrap();
+/// let flavored = release.flavored();
+/// assert_eq!(flavored.flavor(), Some("debug"));
+/// assert_eq!(flavored.base_package(), Some("com.acme.app"));
+/// assert_eq!(flavored.base_version().unwrap().raw(), "1.2.3");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlavoredRelease<'a> {
+    release: Release<'a>,
+    base_package: Option<&'a str>,
+    base_version: Option<OwnedVersion>,
+    flavor: Option<&'a str>,
+    location: Option<FlavorLocation>,
+}
+
+impl<'a> FlavoredRelease<'a> {
+    /// Returns the release.
+    pub fn release(&self) -> &Release<'a> {
+        &self.release
+    }
+
+    /// Returns the package without the flavor.
+    pub fn base_package(&self) -> Option<&'a str> {
+        self.base_package
+    }
+
+    /// Returns the version without the flavor.
+    pub fn base_version(&self) -> Option<&OwnedVersion> {
+        self.base_version.as_ref()
+    }
+
+    /// Returns the flavor as it appears in the release.
+    pub fn flavor(&self) -> Option<&'a str> {
+        self.flavor
+    }
+
+    /// Returns where the flavor was found.
+    pub fn location(&self) -> Option<FlavorLocation> {
+        self.location
+    }
+
+    /// Compares the base versions of two releases of the same base package.
+    ///
+    /// Returns `None` if the base packages differ or either release does
+    /// not have a version.
+    pub fn cmp_ignoring_flavor(&self, other: &FlavoredRelease<'_>) -> Option<Ordering> {
+        if self.base_package != other.base_package {
+            return None;
+        }
+        Some(
+            self.base_version
+                .as_ref()?
+                .cmp(other.base_version.as_ref()?),
+        )
+    }
+}
@@ -0,0 +1,39 @@
+/// Matches a value against a pattern where `*` matches any sequence of
+/// characters.
+///
+/// All other characters match themselves.
+pub(crate) fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let value = value.as_bytes();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(&c) if c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // let the last star swallow one more character
+                Some((star, matched)) => {
+                    p = star + 1;
+                    v = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Returns `true` if the pattern contains a wildcard.
+pub(crate) fn is_glob(pattern: &str) -> bool {
+    pattern.contains('*')
+}
@@ -19,10 +19,97 @@
 //! # Features
 //!
 //! - `semver`: if enabled the version object provides a method to convert it
-//!   into a semver (0.9 API) if it's compatible.
+//!   into a semver (0.9 API) if it's compatible and semver versions can be
+//!   converted into an `OwnedVersion`.
 //! - `semver-1`: if enabled the version object provide a method to convert it
-//!   into a semver (1.0+ API) if it's compatible.
-//! - `serde`: turns on serde serialization.
+//!   into a semver (1.0+ API) if it's compatible.  `TryFrom` fails instead
+//!   of dropping parts of the version and semver versions can be converted
+//!   into an `OwnedVersion`.
+//! - `serde`: turns on serde serialization and deserialization of the
+//!   `ParserConfig`.
+//! - `unicode`: enables the `UnicodePolicy` which rejects invisible and
+//!   bidirectional control characters, unnormalized text and packages mixing
+//!   scripts, and the `normalize_nfc` helper.
+mod columns;
+mod config;
+#[cfg(any(feature = "semver", feature = "semver-1"))]
+mod convert;
+mod diff;
+mod environment;
+mod explain;
+mod flavor;
+mod glob;
+mod lint;
+mod npm;
+mod package;
 mod parser;
+mod pattern;
+mod pep440;
+mod release_set;
+mod sanitize;
+mod scheme;
+mod search;
+mod sensitive;
+mod sort_key;
+mod unicode;
+mod validation;
 
+pub use self::columns::*;
+pub use self::config::*;
+#[cfg(any(feature = "semver", feature = "semver-1"))]
+// with only `semver` enabled the module only has trait impls
+#[allow(unused_imports)]
+pub use self::convert::*;
+pub use self::diff::*;
+pub use self::environment::*;
+pub use self::explain::*;
+pub use self::flavor::*;
+pub use self::lint::*;
+pub use self::npm::*;
+pub use self::package::*;
 pub use self::parser::*;
+pub use self::pattern::*;
+pub use self::pep440::*;
+pub use self::release_set::*;
+pub use self::sanitize::*;
+pub use self::scheme::*;
+pub use self::search::*;
+pub use self::sensitive::*;
+pub use self::sort_key::*;
+pub use self::unicode::*;
+pub use self::validation::*;
+
+/// Returns the patterns of all regular expressions used by the crate.
+///
+/// This is not public API.  The crate depends on `regex` without its
+/// unicode features but the dev dependencies enable them for the tests,
+/// so the tests check these patterns with `regex-syntax` instead.
+#[doc(hidden)]
+pub fn __regex_patterns() -> Vec<&'static str> {
+    let regexes: [&'static regex::Regex; 23] = [
+        &*explain::COMPONENTS_REGEX,
+        &*explain::PRERELEASE_REGEX,
+        &*explain::BUILD_CODE_REGEX,
+        &*npm::HYPHEN_REGEX,
+        &*npm::OPERATOR_SPACE_REGEX,
+        &*npm::PARTIAL_REGEX,
+        &*parser::RELEASE_REGEX,
+        &*parser::VERSION_REGEX,
+        &*parser::HEX_REGEX,
+        &*parser::UUID_REGEX,
+        &*parser::TIMESTAMP_REGEX,
+        &*parser::CALVER_REGEX,
+        &*pep440::PEP440_REGEX,
+        &*pep440::SPECIFIER_REGEX,
+        &*scheme::SEMVER_REGEX,
+        &*search::KEY_REGEX,
+        &*sensitive::TOKEN_REGEX,
+        &*sensitive::AWS_KEY_REGEX,
+        &*sensitive::EMAIL_REGEX,
+        &*sensitive::IP_REGEX,
+        &*sensitive::INTERNAL_HOST_REGEX,
+        &*sensitive::CANDIDATE_TOKEN_REGEX,
+        &*validation::VALID_API_ATTRIBUTE_REGEX,
+    ];
+    regexes.iter().map(|regex| regex.as_str()).collect()
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::environment::{EnvironmentTier, TierAliases};
+use crate::parser::Release;
+
+/// How serious a lint warning is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LintSeverity {
+    /// The release is valid but likely not what was intended.
+    Warning,
+    /// The release is rejected by Sentry.
+    Error,
+}
+
+impl LintSeverity {
+    /// Returns the name of the severity.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            LintSeverity::Warning => "warning",
+            LintSeverity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Identifies what a lint warning is about.
+///
+/// The string form returned by [`as_str`](Self::as_str) is stable and can be
+/// used to suppress warnings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LintCode {
+    /// The release is not valid.
+    InvalidRelease,
+    /// The release has leading or trailing whitespace that is trimmed.
+    SurroundingWhitespace,
+    /// The release is a build hash without a package.
+    BareHash,
+    /// The release ends in `@` or its version has no letters or digits.
+    EmptyVersion,
+    /// The pre-release of the version is named like a production or staging
+    /// environment.
+    EnvironmentPrerelease,
+    /// The package contains whitespace.
+    PackageWhitespace,
+    /// The release differs from another release only in case.
+    CaseConflict,
+}
+
+impl LintCode {
+    /// Returns the stable name of the code.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            LintCode::InvalidRelease => "invalid_release",
+            LintCode::SurroundingWhitespace => "surrounding_whitespace",
+            LintCode::BareHash => "bare_hash",
+            LintCode::EmptyVersion => "empty_version",
+            LintCode::EnvironmentPrerelease => "environment_prerelease",
+            LintCode::PackageWhitespace => "package_whitespace",
+            LintCode::CaseConflict => "case_conflict",
+        }
+    }
+
+    /// Returns the severity of warnings with this code.
+    pub fn severity(&self) -> LintSeverity {
+        match *self {
+            LintCode::InvalidRelease => LintSeverity::Error,
+            _ => LintSeverity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A problem found in a release name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    code: LintCode,
+    message: String,
+}
+
+impl LintWarning {
+    fn new(code: LintCode, message: String) -> LintWarning {
+        LintWarning { code, message }
+    }
+
+    /// Returns the code of the warning.
+    pub fn code(&self) -> LintCode {
+        self.code
+    }
+
+    /// Returns the severity of the warning.
+    pub fn severity(&self) -> LintSeverity {
+        self.code.severity()
+    }
+
+    /// Returns a human readable description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity(), self.code, self.message)
+    }
+}
+
+/// Checks a release for names that are valid but likely mistakes.
+///
+/// Invalid releases produce a single [`LintCode::InvalidRelease`] error
+/// (after a possible whitespace warning) as the other checks need a parsed
+/// release.
+///
+/// ```
+/// use sentry_release_parser::{lint_release, LintCode};
+///
+/// let warnings = lint_release("myapp@1.0-production");
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].code(), LintCode::EnvironmentPrerelease);
+/// assert_eq!(warnings[0].code().as_str(), "environment_prerelease");
+/// ```
+pub fn lint_release(release: &str) -> Vec<LintWarning> {
+    let mut rv = vec![];
+
+    if release.trim() != release {
+        rv.push(LintWarning::new(
+            LintCode::SurroundingWhitespace,
+            format!("leading or trailing whitespace in {:?} is trimmed", release),
+        ));
+    }
+
+    let parsed = match Release::parse(release) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            rv.push(LintWarning::new(LintCode::InvalidRelease, err.to_string()));
+            return rv;
+        }
+    };
+
+    if parsed.package().is_none() && parsed.build_hash().is_some() {
+        rv.push(LintWarning::new(
+            LintCode::BareHash,
+            "release is a build hash without a package".into(),
+        ));
+    }
+
+    if parsed.raw().ends_with('@')
+        || (parsed.package().is_some() && !parsed.version_raw().chars().any(char::is_alphanumeric))
+    {
+        rv.push(LintWarning::new(
+            LintCode::EmptyVersion,
+            format!("release {:?} has an empty version", parsed.raw()),
+        ));
+    }
+
+    if let Some(package) = parsed.package() {
+        if package.chars().any(char::is_whitespace) {
+            rv.push(LintWarning::new(
+                LintCode::PackageWhitespace,
+                format!("package {:?} contains whitespace", package),
+            ));
+        }
+    }
+
+    if let Some(pre) = parsed.version().and_then(|v| v.pre()) {
+        // pre-releases such as beta, dev or test are common and fine, only
+        // deployment targets are suspicious
+        let tier = TierAliases::default().tier(pre);
+        if let Some(tier @ (EnvironmentTier::Production | EnvironmentTier::Staging)) = tier {
+            rv.push(LintWarning::new(
+                LintCode::EnvironmentPrerelease,
+                format!("pre-release {:?} looks like a {} environment", pre, tier),
+            ));
+        }
+    }
+
+    rv
+}
+
+/// Checks a list of releases.
+///
+/// Returns the warnings of [`lint_release`] for every release in the same
+/// order, plus a [`LintCode::CaseConflict`] warning for releases that
+/// differ from an earlier release only in case.
+pub fn lint_releases<'a, I>(releases: I) -> Vec<Vec<LintWarning>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut seen = HashMap::new();
+    releases
+        .into_iter()
+        .map(|release| {
+            let mut warnings = lint_release(release);
+            let trimmed = release.trim();
+            match seen.get(&trimmed.to_lowercase()) {
+                Some(&other) if other != trimmed => warnings.push(LintWarning::new(
+                    LintCode::CaseConflict,
+                    format!("release differs from {:?} only in case", other),
+                )),
+                Some(_) => {}
+                None => {
+                    seen.insert(trimmed.to_lowercase(), trimmed);
+                }
+            }
+            warnings
+        })
+        .collect()
+}
@@ -0,0 +1,457 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::parser::Version;
+
+lazy_static! {
+    pub(crate) static ref HYPHEN_REGEX: Regex =
+        Regex::new(r"^([^ \t]+)[ \t]+-[ \t]+([^ \t]+)$").unwrap();
+    pub(crate) static ref OPERATOR_SPACE_REGEX: Regex =
+        Regex::new(r"(<=|>=|~>|[<>=~^])[ \t]+").unwrap();
+    pub(crate) static ref PARTIAL_REGEX: Regex = Regex::new(
+        r"(?x)
+        ^
+            v?
+            (?P<major>[0-9]+|[xX*])
+            (?:\.(?P<minor>[0-9]+|[xX*])
+                (?:\.(?P<patch>[0-9]+|[xX*])
+                    (?:-?(?P<pre>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?
+                    (?:\+[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*)?
+                )?
+            )?
+        $
+        "
+    )
+    .unwrap();
+}
+
+/// An error indicating an invalid npm range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidRange;
+
+impl std::error::Error for InvalidRange {}
+
+impl fmt::Display for InvalidRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid range")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Identifier {
+    fn parse(s: &str) -> Identifier {
+        if !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()) {
+            if let Ok(num) = s.parse() {
+                return Identifier::Numeric(num);
+            }
+        }
+        Identifier::AlphaNumeric(s.to_string())
+    }
+
+    pub(crate) fn split(s: &str) -> Vec<Identifier> {
+        if s.is_empty() {
+            vec![]
+        } else {
+            s.split('.').map(Identifier::parse).collect()
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Identifier::Numeric(num) => write!(f, "{}", num),
+            Identifier::AlphaNumeric(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Compares two pre-releases with semver precedence.
+pub(crate) fn cmp_pre(a: &[Identifier], b: &[Identifier]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.cmp(b),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Bound {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<Identifier>,
+}
+
+impl Bound {
+    fn new(major: u64, minor: u64, patch: u64) -> Bound {
+        Bound {
+            major,
+            minor,
+            patch,
+            pre: vec![],
+        }
+    }
+
+    /// Creates the lowest possible version of a triple (`1.2.3-0`).
+    fn lowest(major: u64, minor: u64, patch: u64) -> Bound {
+        Bound {
+            major,
+            minor,
+            patch,
+            pre: vec![Identifier::Numeric(0)],
+        }
+    }
+
+    fn triple(&self) -> (u64, u64, u64) {
+        (self.major, self.minor, self.patch)
+    }
+
+    /// Compares a version to the bound.
+    ///
+    /// The revision is compared as a fourth component which is always zero
+    /// for the bound.
+    fn cmp_version(&self, version: &Version<'_>, pre: &[Identifier]) -> Ordering {
+        version
+            .triple()
+            .cmp(&self.triple())
+            .then_with(|| version.revision().cmp(&0))
+            .then_with(|| cmp_pre(pre, &self.pre))
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        for (idx, ident) in self.pre.iter().enumerate() {
+            write!(f, "{}{}", if idx == 0 { '-' } else { '.' }, ident)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Comparator {
+    Any,
+    Nothing,
+    Cmp(Op, Bound),
+}
+
+impl Comparator {
+    fn test(&self, version: &Version<'_>, pre: &[Identifier]) -> bool {
+        match self {
+            Comparator::Any => true,
+            Comparator::Nothing => false,
+            Comparator::Cmp(op, bound) => {
+                let ord = bound.cmp_version(version, pre);
+                match op {
+                    Op::Eq => ord == Ordering::Equal,
+                    Op::Lt => ord == Ordering::Less,
+                    Op::Le => ord != Ordering::Greater,
+                    Op::Gt => ord == Ordering::Greater,
+                    Op::Ge => ord != Ordering::Less,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Comparator::Any => write!(f, "*"),
+            Comparator::Nothing => write!(f, "<0.0.0-0"),
+            Comparator::Cmp(op, bound) => {
+                let op = match op {
+                    Op::Eq => "",
+                    Op::Lt => "<",
+                    Op::Le => "<=",
+                    Op::Gt => ">",
+                    Op::Ge => ">=",
+                };
+                write!(f, "{}{}", op, bound)
+            }
+        }
+    }
+}
+
+/// A partial version as it appears in ranges (`1`, `1.2.x`, `1.2.3-rc1`).
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<Identifier>,
+}
+
+impl Partial {
+    fn parse(s: &str) -> Result<Partial, InvalidRange> {
+        let s = s.trim_start_matches('=');
+        let caps = PARTIAL_REGEX.captures(s).ok_or(InvalidRange)?;
+        let component = |name| -> Result<Option<u64>, InvalidRange> {
+            match caps.name(name).map(|x| x.as_str()) {
+                None | Some("x") | Some("X") | Some("*") => Ok(None),
+                Some(num) => num.parse().map(Some).map_err(|_| InvalidRange),
+            }
+        };
+
+        // once a component is a wildcard, all following ones are too.
+        let major = component("major")?;
+        let minor = major.and(component("minor")?);
+        let patch = minor.and(component("patch")?);
+        let pre = match patch {
+            Some(_) => Identifier::split(caps.name("pre").map_or("", |x| x.as_str())),
+            None => vec![],
+        };
+
+        Ok(Partial {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    fn bound(&self) -> Bound {
+        Bound {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+        }
+    }
+}
+
+/// Returns the next component, failing for components that cannot be bumped.
+fn next(component: u64) -> Result<u64, InvalidRange> {
+    component.checked_add(1).ok_or(InvalidRange)
+}
+
+fn parse_primitive(op: &str, partial: Partial) -> Result<Vec<Comparator>, InvalidRange> {
+    let (major, minor, patch) = match (partial.major, partial.minor, partial.patch) {
+        (Some(_), Some(_), Some(_)) => {
+            let op = match op {
+                "<" => Op::Lt,
+                "<=" => Op::Le,
+                ">" => Op::Gt,
+                ">=" => Op::Ge,
+                _ => Op::Eq,
+            };
+            return Ok(vec![Comparator::Cmp(op, partial.bound())]);
+        }
+        (None, _, _) => {
+            return Ok(vec![match op {
+                "<" | ">" => Comparator::Nothing,
+                _ => Comparator::Any,
+            }]);
+        }
+        (Some(major), minor, _) => (major, minor, partial.patch),
+    };
+
+    Ok(match (op, minor) {
+        (">", None) => vec![Comparator::Cmp(Op::Ge, Bound::new(next(major)?, 0, 0))],
+        (">", Some(minor)) => vec![Comparator::Cmp(Op::Ge, Bound::new(major, next(minor)?, 0))],
+        ("<=", None) => vec![Comparator::Cmp(Op::Lt, Bound::lowest(next(major)?, 0, 0))],
+        ("<=", Some(minor)) => vec![Comparator::Cmp(
+            Op::Lt,
+            Bound::lowest(major, next(minor)?, 0),
+        )],
+        ("<", minor) => vec![Comparator::Cmp(
+            Op::Lt,
+            Bound::lowest(major, minor.unwrap_or(0), patch.unwrap_or(0)),
+        )],
+        (">=", minor) => vec![Comparator::Cmp(
+            Op::Ge,
+            Bound::new(major, minor.unwrap_or(0), 0),
+        )],
+        (_, None) => vec![
+            Comparator::Cmp(Op::Ge, Bound::new(major, 0, 0)),
+            Comparator::Cmp(Op::Lt, Bound::lowest(next(major)?, 0, 0)),
+        ],
+        (_, Some(minor)) => vec![
+            Comparator::Cmp(Op::Ge, Bound::new(major, minor, 0)),
+            Comparator::Cmp(Op::Lt, Bound::lowest(major, next(minor)?, 0)),
+        ],
+    })
+}
+
+fn parse_tilde(partial: Partial) -> Result<Vec<Comparator>, InvalidRange> {
+    Ok(match (partial.major, partial.minor) {
+        (None, _) => vec![Comparator::Any],
+        (Some(major), None) => vec![
+            Comparator::Cmp(Op::Ge, Bound::new(major, 0, 0)),
+            Comparator::Cmp(Op::Lt, Bound::lowest(next(major)?, 0, 0)),
+        ],
+        (Some(major), Some(minor)) => vec![
+            Comparator::Cmp(Op::Ge, partial.bound()),
+            Comparator::Cmp(Op::Lt, Bound::lowest(major, next(minor)?, 0)),
+        ],
+    })
+}
+
+fn parse_caret(partial: Partial) -> Result<Vec<Comparator>, InvalidRange> {
+    let upper = match (partial.major, partial.minor, partial.patch) {
+        (None, _, _) => return Ok(vec![Comparator::Any]),
+        (Some(major), None, _) => Bound::lowest(next(major)?, 0, 0),
+        (Some(0), Some(minor), None) => Bound::lowest(0, next(minor)?, 0),
+        (Some(0), Some(0), Some(patch)) => Bound::lowest(0, 0, next(patch)?),
+        (Some(0), Some(minor), Some(_)) => Bound::lowest(0, next(minor)?, 0),
+        (Some(major), Some(_), _) => Bound::lowest(next(major)?, 0, 0),
+    };
+    Ok(vec![
+        Comparator::Cmp(Op::Ge, partial.bound()),
+        Comparator::Cmp(Op::Lt, upper),
+    ])
+}
+
+fn parse_hyphen(from: Partial, to: Partial) -> Result<Vec<Comparator>, InvalidRange> {
+    let mut rv = vec![];
+    if from.major.is_some() {
+        rv.push(Comparator::Cmp(Op::Ge, from.bound()));
+    }
+    match (to.major, to.minor, to.patch) {
+        (None, _, _) => {}
+        (Some(major), None, _) => {
+            rv.push(Comparator::Cmp(Op::Lt, Bound::lowest(next(major)?, 0, 0)))
+        }
+        (Some(major), Some(minor), None) => rv.push(Comparator::Cmp(
+            Op::Lt,
+            Bound::lowest(major, next(minor)?, 0),
+        )),
+        (Some(_), Some(_), Some(_)) => rv.push(Comparator::Cmp(Op::Le, to.bound())),
+    }
+    if rv.is_empty() {
+        rv.push(Comparator::Any);
+    }
+    Ok(rv)
+}
+
+fn parse_comparator(token: &str) -> Result<Vec<Comparator>, InvalidRange> {
+    for prefix in &["~>", "~", "^", "<=", ">=", "<", ">"] {
+        if let Some(rest) = token.strip_prefix(prefix) {
+            let partial = Partial::parse(rest)?;
+            return match *prefix {
+                "~>" | "~" => parse_tilde(partial),
+                "^" => parse_caret(partial),
+                op => parse_primitive(op, partial),
+            };
+        }
+    }
+    parse_primitive("=", Partial::parse(token)?)
+}
+
+fn parse_set(range: &str) -> Result<Vec<Comparator>, InvalidRange> {
+    let range = range.trim();
+    if let Some(caps) = HYPHEN_REGEX.captures(range) {
+        return parse_hyphen(Partial::parse(&caps[1])?, Partial::parse(&caps[2])?);
+    }
+
+    let range = OPERATOR_SPACE_REGEX.replace_all(range, "$1");
+    let mut rv = vec![];
+    for token in range.split_whitespace() {
+        rv.extend(parse_comparator(token)?);
+    }
+    if rv.is_empty() {
+        rv.push(Comparator::Any);
+    }
+    Ok(rv)
+}
+
+/// A node-semver version range.
+///
+/// This understands the range syntax of npm: primitive comparators
+/// (`>=1.2.0 <2.0.0`), X-ranges (`1.x`, `1.2.*`, `*`), tilde ranges
+/// (`~1.2.3`), caret ranges (`^1.2.3`), hyphen ranges (`1.2.3 - 2.3.4`) and
+/// unions of ranges (`^1.0.0 || ^2.0.0`).
+///
+/// Versions with a pre-release only match if one of the comparators of the
+/// matching range names a pre-release of the same `major.minor.patch`
+/// triple, like in node-semver.  So `1.3.0-rc1` does not match `^1.2.0`
+/// but `1.2.5-rc1` matches `>=1.2.5-beta`.  Pre-release identifiers are
+/// compared with semver precedence.  The revision of four component
+/// versions is compared as an additional component that is always zero in
+/// ranges, so `1.2.3.4` matches `>1.2.3`.
+///
+/// ```
+/// use sentry_release_parser::{NpmRange, Version};
+///
+/// let range = NpmRange::parse("^3.2.0").unwrap();
+/// assert!(range.matches(&Version::parse("3.4.1").unwrap()));
+/// assert!(!range.matches(&Version::parse("4.0.0").unwrap()));
+/// assert!(!range.matches(&Version::parse("3.5.0-beta.1").unwrap()));
+/// assert_eq!(range.to_string(), ">=3.2.0 <4.0.0-0");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NpmRange {
+    sets: Vec<Vec<Comparator>>,
+}
+
+impl NpmRange {
+    /// Parses a range from a string.
+    pub fn parse(range: &str) -> Result<NpmRange, InvalidRange> {
+        Ok(NpmRange {
+            sets: range.split("||").map(parse_set).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Checks if a version satisfies the range.
+    pub fn matches(&self, version: &Version<'_>) -> bool {
+        let pre = Identifier::split(version.pre().unwrap_or(""));
+        self.sets.iter().any(|set| {
+            if !set.iter().all(|comparator| comparator.test(version, &pre)) {
+                return false;
+            }
+            pre.is_empty()
+                || set.iter().any(|comparator| match comparator {
+                    Comparator::Cmp(_, bound) => {
+                        !bound.pre.is_empty() && bound.triple() == version.triple()
+                    }
+                    _ => false,
+                })
+        })
+    }
+}
+
+impl FromStr for NpmRange {
+    type Err = InvalidRange;
+
+    fn from_str(s: &str) -> Result<NpmRange, InvalidRange> {
+        NpmRange::parse(s)
+    }
+}
+
+/// Formats the range in its desugared comparator form.
+impl fmt::Display for NpmRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (set_idx, set) in self.sets.iter().enumerate() {
+            if set_idx > 0 {
+                write!(f, " || ")?;
+            }
+            for (idx, comparator) in set.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", comparator)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Top level domains that make a two segment package a reverse-DNS name.
+const COMMON_TLDS: [&str; 6] = ["com", "org", "net", "io", "dev", "app"];
+
+/// The maximum length of npm package names.
+const NPM_MAX_LENGTH: usize = 214;
+
+/// An error indicating a package that violates the naming rules of its
+/// ecosystem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidPackage {
+    /// The package name is empty
+    Empty,
+    /// The package name is too long
+    TooLong,
+    /// The package name contains uppercase characters
+    Uppercase,
+    /// The package name contains invalid characters
+    BadCharacters,
+    /// A segment of the package name is empty
+    EmptySegment,
+    /// A segment of the package name does not start with a letter
+    InvalidSegmentStart,
+}
+
+impl std::error::Error for InvalidPackage {}
+
+impl fmt::Display for InvalidPackage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid package: {}",
+            match *self {
+                InvalidPackage::Empty => "empty package name",
+                InvalidPackage::TooLong => "package name too long",
+                InvalidPackage::Uppercase => "uppercase characters in package name",
+                InvalidPackage::BadCharacters => "bad characters in package name",
+                InvalidPackage::EmptySegment => "empty segment in package name",
+                InvalidPackage::InvalidSegmentStart => {
+                    "package name segment does not start with a letter"
+                }
+            }
+        )
+    }
+}
+
+/// The kind of a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PackageKind {
+    /// A scoped npm package such as `@sentry/browser`.
+    NpmScoped,
+    /// A reverse-DNS bundle or application id such as `org.example.FooApp`.
+    ReverseDns,
+    /// Any other package name.
+    Plain,
+}
+
+/// A package with its structure.
+///
+/// ```
+/// use sentry_release_parser::{PackageKind, Release};
+///
+/// let release = Release::parse("org.example.FooApp@1.0").unwrap();
+/// let package = release.package_info().unwrap();
+/// assert_eq!(package.kind(), PackageKind::ReverseDns);
+/// assert_eq!(package.scope(), Some("org.example"));
+/// assert_eq!(package.segments(), ["org", "example", "FooApp"]);
+/// assert_eq!(package.display_name(), "FooApp");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Package<'a> {
+    raw: &'a str,
+    kind: PackageKind,
+}
+
+impl<'a> Package<'a> {
+    /// Classifies a package name.
+    ///
+    /// - names starting with `@` that contain a `/` are scoped npm packages.
+    /// - dotted names with at least three segments, or two segments starting
+    ///   with a common top level domain (`com.acme`), are reverse-DNS names.
+    /// - everything else is a plain package.
+    pub fn parse(package: &'a str) -> Package<'a> {
+        let kind = if package.starts_with('@') && package.contains('/') {
+            PackageKind::NpmScoped
+        } else {
+            let segments = package.split('.').count();
+            let first = package.split('.').next().unwrap_or_default();
+            if segments >= 3 || (segments == 2 && COMMON_TLDS.contains(&first)) {
+                PackageKind::ReverseDns
+            } else {
+                PackageKind::Plain
+            }
+        };
+        Package { raw: package, kind }
+    }
+
+    /// Returns the package name.
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    /// Returns the kind of the package.
+    pub fn kind(&self) -> PackageKind {
+        self.kind
+    }
+
+    /// Returns the scope of the package.
+    ///
+    /// For scoped npm packages this is the scope without the `@`.  For
+    /// reverse-DNS names it is the domain part without the last segment.
+    /// Plain packages have no scope.
+    pub fn scope(&self) -> Option<&'a str> {
+        match self.kind {
+            PackageKind::NpmScoped => self.raw[1..].split('/').next(),
+            PackageKind::ReverseDns => self.raw.rsplit_once('.').map(|x| x.0),
+            PackageKind::Plain => None,
+        }
+    }
+
+    /// Returns the segments of the package name.
+    ///
+    /// Scoped npm packages are split into scope and name, reverse-DNS names
+    /// at the dots.  Plain packages have a single segment.
+    pub fn segments(&self) -> Vec<&'a str> {
+        match self.kind {
+            PackageKind::NpmScoped => self.raw[1..].splitn(2, '/').collect(),
+            PackageKind::ReverseDns => self.raw.split('.').collect(),
+            PackageKind::Plain => vec![self.raw],
+        }
+    }
+
+    /// Returns the last segment as name to display.
+    pub fn display_name(&self) -> &'a str {
+        self.segments().last().copied().unwrap_or(self.raw)
+    }
+
+    /// Validates the package against the naming rules of its ecosystem.
+    ///
+    /// - scoped npm packages follow the npm rules: at most 214 characters,
+    ///   lowercase, URL safe characters and no name starting with `.` or
+    ///   `_`.
+    /// - reverse-DNS names follow the rules shared by Android application
+    ///   ids and Apple bundle ids: segments start with a letter and consist
+    ///   of letters, digits, `_` and `-`.
+    /// - plain packages must not contain whitespace.
+    pub fn validate(&self) -> Result<(), InvalidPackage> {
+        if self.raw.is_empty() {
+            return Err(InvalidPackage::Empty);
+        }
+        match self.kind {
+            PackageKind::NpmScoped => {
+                if self.raw.len() > NPM_MAX_LENGTH {
+                    return Err(InvalidPackage::TooLong);
+                }
+                for segment in self.segments() {
+                    if segment.is_empty() {
+                        return Err(InvalidPackage::EmptySegment);
+                    } else if segment.starts_with(&['.', '_'][..]) {
+                        return Err(InvalidPackage::InvalidSegmentStart);
+                    } else if segment.bytes().any(|c| c.is_ascii_uppercase()) {
+                        return Err(InvalidPackage::Uppercase);
+                    } else if !segment
+                        .bytes()
+                        .all(|c| c.is_ascii_alphanumeric() || b"-._~".contains(&c))
+                    {
+                        return Err(InvalidPackage::BadCharacters);
+                    }
+                }
+            }
+            PackageKind::ReverseDns => {
+                for segment in self.segments() {
+                    if segment.is_empty() {
+                        return Err(InvalidPackage::EmptySegment);
+                    } else if !segment.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                        return Err(InvalidPackage::InvalidSegmentStart);
+                    } else if !segment
+                        .bytes()
+                        .all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-')
+                    {
+                        return Err(InvalidPackage::BadCharacters);
+                    }
+                }
+            }
+            PackageKind::Plain => {
+                if self.raw.chars().any(char::is_whitespace) {
+                    return Err(InvalidPackage::BadCharacters);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares two packages ignoring ASCII case.
+    ///
+    /// Bundle ids are case-insensitive on some platforms, so
+    /// `org.example.FooApp` and `org.example.fooapp` refer to the same app.
+    pub fn eq_ignore_case(&self, other: &Package<'_>) -> bool {
+        self.raw.eq_ignore_ascii_case(other.raw)
+    }
+}
+
+impl<'a> fmt::Display for Package<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
@@ -1,8 +1,27 @@
-use std::{cmp::Ordering, fmt};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::columns::ReleaseColumns;
+#[cfg(feature = "semver-1")]
+use crate::convert::{Conversion, LossyConversion};
+use crate::diff::{ReleaseChange, VersionChange};
+use crate::explain::ReleaseExplanation;
+use crate::flavor::{FlavorConfig, FlavoredRelease};
+use crate::package::Package;
+use crate::scheme::{Parser, SchemeRef, VersionScheme};
+use crate::sort_key::SortKey;
+use crate::unicode::UnicodeIssue;
+use crate::validation::{
+    validate_environment_with, validate_release_with, ValidationConfig,
+    DEFAULT_ENVIRONMENT_VALIDATION, DEFAULT_RELEASE_VALIDATION,
+};
+
 #[cfg(feature = "serde")]
 use serde::{
     ser::{SerializeStruct, Serializer},
@@ -13,8 +32,8 @@ use serde::{
 use semver_1::{BuildMetadata, Prerelease};
 
 lazy_static! {
-    static ref RELEASE_REGEX: Regex = Regex::new(r#"^(@?[^@]+)@(.+?)$"#).unwrap();
-    static ref VERSION_REGEX: Regex = Regex::new(
+    pub(crate) static ref RELEASE_REGEX: Regex = Regex::new(r#"^(@?[^@]+)@(.+?)$"#).unwrap();
+    pub(crate) static ref VERSION_REGEX: Regex = Regex::new(
         r"(?x)
         ^
             (?P<major>[0-9][0-9]*)
@@ -32,10 +51,27 @@ lazy_static! {
         "
     )
     .unwrap();
-    static ref HEX_REGEX: Regex = Regex::new(r#"^[a-fA-F0-9]+$"#).unwrap();
-    // what can or cannot go through the API which is a limiting factor for
-    // releases and environments.
-    static ref VALID_API_ATTRIBUTE_REGEX: Regex = Regex::new(r"^[^\\/\r\n\t\x7f\x00-\x1f]*\z").unwrap();
+    pub(crate) static ref HEX_REGEX: Regex = Regex::new(r#"^[a-fA-F0-9]+$"#).unwrap();
+    pub(crate) static ref UUID_REGEX: Regex = Regex::new(
+        r"^[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12}$"
+    )
+    .unwrap();
+    pub(crate) static ref TIMESTAMP_REGEX: Regex = Regex::new(
+        r"(?x)
+        ^(?:
+            # unix timestamps in seconds or milliseconds, or YYYYMMDDHHMMSS
+            [0-9]{10} | [0-9]{13} | [0-9]{14}
+            |
+            # a date followed by a time
+            [0-9]{4}[-.]?[0-9]{2}[-.]?[0-9]{2}
+            [T_.-]
+            [0-9]{2}[:.-]?[0-9]{2}(?:[:.-]?[0-9]{2})?Z?
+        )$
+        "
+    )
+    .unwrap();
+    pub(crate) static ref CALVER_REGEX: Regex =
+        Regex::new(r"^(?:19[7-9][0-9]|2[0-9]{3})\.(?:0?[1-9]|1[0-2])(?:[.+-].*)?$").unwrap();
 }
 
 /// An error indicating invalid versions.
@@ -52,40 +88,84 @@ impl fmt::Display for InvalidVersion {
 }
 
 /// An error indicating invalid releases.
+///
+/// The `Display` implementation only names the violated rule.  The alternate
+/// form (`{:#}`) adds the measured length or the offending character.
 #[derive(Debug, Clone, PartialEq)]
 pub enum InvalidRelease {
     /// The release name was too long
-    TooLong,
+    TooLong {
+        /// The length of the release in the unit of the validation rules
+        length: usize,
+        /// The maximum allowed length
+        limit: usize,
+    },
     /// Release name is restricted
     RestrictedName,
     /// The release contained invalid characters
-    BadCharacters,
+    BadCharacters {
+        /// The first invalid character
+        character: char,
+        /// The byte offset of the invalid character
+        offset: usize,
+    },
+    /// The release was rejected by the unicode policy
+    UnsafeUnicode(UnicodeIssue),
 }
 
 /// An error indicating invalid environment.
+///
+/// The `Display` implementation only names the violated rule.  The alternate
+/// form (`{:#}`) adds the measured length or the offending character.
 #[derive(Debug, Clone, PartialEq)]
 pub enum InvalidEnvironment {
     /// The environment name was too long
-    TooLong,
+    TooLong {
+        /// The length of the environment in the unit of the validation rules
+        length: usize,
+        /// The maximum allowed length
+        limit: usize,
+    },
     /// Environment name is restricted
     RestrictedName,
     /// The environment contained invalid characters
-    BadCharacters,
+    BadCharacters {
+        /// The first invalid character
+        character: char,
+        /// The byte offset of the invalid character
+        offset: usize,
+    },
+    /// The environment was rejected by the unicode policy
+    UnsafeUnicode(UnicodeIssue),
+}
+
+/// Writes the details of a validation error for the alternate format.
+fn write_violation_details(f: &mut fmt::Formatter, details: fmt::Arguments) -> fmt::Result {
+    if f.alternate() {
+        write!(f, " ({})", details)?;
+    }
+    Ok(())
 }
 
 impl std::error::Error for InvalidRelease {}
 
 impl fmt::Display for InvalidRelease {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "invalid release: {}",
-            match *self {
-                InvalidRelease::BadCharacters => "bad characters in release name",
-                InvalidRelease::RestrictedName => "restricted release name",
-                InvalidRelease::TooLong => "release name too long",
+        match *self {
+            InvalidRelease::BadCharacters { character, offset } => {
+                write!(f, "invalid release: bad characters in release name")?;
+                write_violation_details(f, format_args!("{:?} at position {}", character, offset))
             }
-        )
+            InvalidRelease::UnsafeUnicode(ref issue) => {
+                write!(f, "invalid release: unsafe unicode in release name")?;
+                write_violation_details(f, format_args!("{}", issue))
+            }
+            InvalidRelease::RestrictedName => write!(f, "invalid release: restricted release name"),
+            InvalidRelease::TooLong { length, limit } => {
+                write!(f, "invalid release: release name too long")?;
+                write_violation_details(f, format_args!("length {}, limit {}", length, limit))
+            }
+        }
     }
 }
 
@@ -93,15 +173,23 @@ impl std::error::Error for InvalidEnvironment {}
 
 impl fmt::Display for InvalidEnvironment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "invalid environment: {}",
-            match *self {
-                InvalidEnvironment::BadCharacters => "bad characters in environment name",
-                InvalidEnvironment::RestrictedName => "restricted environment name",
-                InvalidEnvironment::TooLong => "environment name too long",
+        match *self {
+            InvalidEnvironment::BadCharacters { character, offset } => {
+                write!(f, "invalid environment: bad characters in environment name")?;
+                write_violation_details(f, format_args!("{:?} at position {}", character, offset))
             }
-        )
+            InvalidEnvironment::UnsafeUnicode(ref issue) => {
+                write!(f, "invalid environment: unsafe unicode in environment name")?;
+                write_violation_details(f, format_args!("{}", issue))
+            }
+            InvalidEnvironment::RestrictedName => {
+                write!(f, "invalid environment: restricted environment name")
+            }
+            InvalidEnvironment::TooLong { length, limit } => {
+                write!(f, "invalid environment: environment name too long")?;
+                write_violation_details(f, format_args!("length {}, limit {}", length, limit))
+            }
+        }
     }
 }
 
@@ -139,11 +227,11 @@ impl<'a> Serialize for Version<'a> {
     }
 }
 
-fn is_build_hash(s: &str) -> bool {
-    match s.len() {
-        12 | 16 | 20 | 32 | 40 | 64 => HEX_REGEX.is_match(s),
-        _ => false,
-    }
+/// The lengths of hexadecimal strings that are considered build hashes.
+pub(crate) const BUILD_HASH_LENGTHS: [usize; 6] = [12, 16, 20, 32, 40, 64];
+
+pub(crate) fn is_build_hash(s: &str) -> bool {
+    BUILD_HASH_LENGTHS.contains(&s.len()) && HEX_REGEX.is_match(s)
 }
 
 impl<'a> Version<'a> {
@@ -194,6 +282,90 @@ impl<'a> Version<'a> {
         })
     }
 
+    /// Creates a version from its parts.
+    ///
+    /// This allows custom [`VersionScheme`]s to provide a version view of
+    /// formats that [`Version::parse`] does not understand.  The parts are
+    /// given in the shape returned by [`raw_quad`](Self::raw_quad) and have
+    /// to consist of digits.  The pre-release and build code have to be
+    /// slices of `raw` and may only contain the characters
+    /// [`Version::parse`] accepts for them: ASCII letters, digits and `-`,
+    /// separated by dots.
+    ///
+    /// ```
+    /// use sentry_release_parser::Version;
+    ///
+    /// let raw = "train-42.3";
+    /// let version = Version::from_parts(raw, (&raw[6..8], Some(&raw[9..]), None, None), None, None).unwrap();
+    /// assert_eq!(version.triple(), (42, 3, 0));
+    /// assert_eq!(version.raw(), "train-42.3");
+    /// ```
+    pub fn from_parts(
+        raw: &'a str,
+        quad: (&'a str, Option<&'a str>, Option<&'a str>, Option<&'a str>),
+        pre: Option<&'a str>,
+        build_code: Option<&'a str>,
+    ) -> Result<Version<'a>, InvalidVersion> {
+        let (major, minor, patch, revision) = quad;
+        let is_number = |x: &str| !x.is_empty() && x.bytes().all(|c| c.is_ascii_digit());
+        let components = match (minor, patch, revision) {
+            (None, None, None) => 1,
+            (Some(_), None, None) => 2,
+            (Some(_), Some(_), None) => 3,
+            (Some(_), Some(_), Some(_)) => 4,
+            _ => return Err(InvalidVersion),
+        };
+        if ![Some(major), minor, patch, revision]
+            .iter()
+            .flatten()
+            .all(|x| is_number(x))
+        {
+            return Err(InvalidVersion);
+        }
+
+        let is_identifiers = |x: &str| {
+            x.split('.').all(|ident| {
+                !ident.is_empty()
+                    && ident
+                        .bytes()
+                        .all(|c| c.is_ascii_alphanumeric() || c == b'-')
+            })
+        };
+        let is_slice_of_raw = |x: &str| {
+            let start = raw.as_ptr() as usize;
+            let part = x.as_ptr() as usize;
+            part >= start && part + x.len() <= start + raw.len()
+        };
+        if ![pre, build_code]
+            .iter()
+            .flatten()
+            .all(|x| is_identifiers(x) && is_slice_of_raw(x))
+        {
+            return Err(InvalidVersion);
+        }
+
+        let build_code = build_code.unwrap_or("");
+        let before_code = if build_code.is_empty() {
+            raw
+        } else {
+            raw.strip_suffix(build_code)
+                .and_then(|x| x.strip_suffix('+'))
+                .unwrap_or(raw)
+        };
+
+        Ok(Version {
+            raw,
+            major,
+            minor: minor.unwrap_or(""),
+            patch: patch.unwrap_or(""),
+            revision: revision.unwrap_or(""),
+            pre: pre.unwrap_or(""),
+            before_code,
+            build_code,
+            components,
+        })
+    }
+
     /// Converts the version into a semver (0.9 API).
     ///
     /// Requires the `semver` feature.
@@ -222,6 +394,9 @@ impl<'a> Version<'a> {
 
     /// Converts the version into a semver (1.0 API).
     ///
+    /// Parts of the version that cannot be represented are dropped.  Use
+    /// `semver_1::Version::try_from` to fail instead.
+    ///
     /// Requires the `semver-1` feature.
     #[cfg(feature = "semver-1")]
     pub fn as_semver1(&self) -> semver_1::Version {
@@ -246,6 +421,54 @@ impl<'a> Version<'a> {
         }
     }
 
+    /// Reports what would be lost when converting into a semver (1.0 API).
+    ///
+    /// Requires the `semver-1` feature.
+    #[cfg(feature = "semver-1")]
+    pub fn semver1_conversion(&self) -> Conversion {
+        Conversion::new(
+            self.revision() != 0,
+            !self.pre.is_empty() && Prerelease::new(self.pre).is_err(),
+            !self.build_code.is_empty() && BuildMetadata::new(self.build_code).is_err(),
+        )
+    }
+
+    /// Checks if the version matches a semver (1.0 API) requirement.
+    ///
+    /// The version is matched as its semver conversion with the following
+    /// policy:
+    ///
+    /// - four component versions are matched as their triple if the revision
+    ///   is zero (`1.2.3.0` is matched as `1.2.3`).  A non zero revision
+    ///   cannot be represented and fails.
+    /// - implied pre-releases (`1.0rc1`) are matched like their dashed form
+    ///   (`1.0.0-rc1`).  As with semver they only match comparators that
+    ///   name the same `major.minor.patch` with a pre-release.  Pre-releases
+    ///   that are not valid semver identifiers fail.
+    /// - build codes do not take part in matching and are ignored even if
+    ///   they cannot be represented.
+    ///
+    /// Requires the `semver-1` feature.
+    ///
+    /// ```
+    /// # #[cfg(feature = "semver-1")] {
+    /// use sentry_release_parser::Version;
+    ///
+    /// let req = semver_1::VersionReq::parse(">=1.2.0").unwrap();
+    /// assert_eq!(Version::parse("1.4.0").unwrap().matches_semver_req(&req), Ok(true));
+    /// assert_eq!(Version::parse("1.3.0rc1").unwrap().matches_semver_req(&req), Ok(false));
+    /// assert!(Version::parse("1.2.0.1").unwrap().matches_semver_req(&req).is_err());
+    /// # }
+    /// ```
+    #[cfg(feature = "semver-1")]
+    pub fn matches_semver_req(&self, req: &semver_1::VersionReq) -> Result<bool, LossyConversion> {
+        let conversion = self.semver1_conversion();
+        if conversion.loses_revision() || conversion.loses_pre() {
+            return Err(LossyConversion::new(conversion));
+        }
+        Ok(req.matches(&self.as_semver1()))
+    }
+
     /// Returns the major version component.
     pub fn major(&self) -> u64 {
         self.major.parse().unwrap_or_default()
@@ -330,25 +553,135 @@ impl<'a> Version<'a> {
             (self.components > 3).then_some(self.revision),
         )
     }
+
+    /// Returns a binary key that sorts like the version.
+    ///
+    /// See [`SortKey`] for the details of the encoding.
+    pub fn sort_key(&self) -> Vec<u8> {
+        SortKey::new(self).into_bytes()
+    }
+
+    /// Classifies the change from this version to another version.
+    ///
+    /// The components are compared like in [`quad`](Self::quad),
+    /// [`pre`](Self::pre) and [`build_code`](Self::build_code) and the most
+    /// significant changed component is reported.  If the other version is
+    /// lower, the change is a [`VersionChange::Downgrade`].
+    ///
+    /// ```
+    /// use sentry_release_parser::{Version, VersionChange};
+    ///
+    /// let old = Version::parse("1.2.3").unwrap();
+    /// assert_eq!(old.diff(&Version::parse("1.3.0").unwrap()), VersionChange::Minor);
+    /// assert_eq!(old.diff(&Version::parse("1.2.3+42").unwrap()), VersionChange::Build);
+    /// assert_eq!(old.diff(&Version::parse("1.2.2").unwrap()), VersionChange::Downgrade);
+    /// ```
+    pub fn diff(&self, other: &Version<'_>) -> VersionChange {
+        VersionChange::between(self, other)
+    }
+
+    /// Compares the precedence of two versions.
+    ///
+    /// This is the same as `cmp` but ignores the build code, as build
+    /// metadata does not contribute to the precedence of a version under
+    /// SemVer.  So `1.0.0+20` and `1.0.0+10` have the same precedence.
+    /// Pre-releases have a lower precedence than the release and compare
+    /// lexicographically among each other.
+    pub fn cmp_precedence(&self, other: &Version<'_>) -> Ordering {
+        self.quad()
+            .cmp(&other.quad())
+            .then_with(|| match (self.pre(), other.pre()) {
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (self_pre, other_pre) => self_pre.cmp(&other_pre),
+            })
+    }
+
+    /// Checks if two versions have the same precedence.
+    ///
+    /// See [`cmp_precedence`](Self::cmp_precedence).
+    pub fn eq_precedence(&self, other: &Version<'_>) -> bool {
+        self.cmp_precedence(other) == Ordering::Equal
+    }
+
+    /// Returns a wrapper that compares and hashes by precedence.
+    pub fn precedence(&self) -> Precedence<'a> {
+        Precedence(self.clone())
+    }
+}
+
+/// Wraps a version to compare it by precedence.
+///
+/// The wrapper implements `Ord`, `Eq` and `Hash` with the semantics of
+/// [`Version::cmp_precedence`] so it can be used as a map key where versions
+/// that only differ in their build code should be treated as the same.
+///
+/// ```
+/// use sentry_release_parser::Version;
+///
+/// let a = Version::parse("1.0.0+10").unwrap();
+/// let b = Version::parse("1.0.0+20").unwrap();
+/// assert_ne!(a, b);
+/// assert_eq!(a.precedence(), b.precedence());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Precedence<'a>(Version<'a>);
+
+impl<'a> Precedence<'a> {
+    /// Returns the wrapped version.
+    pub fn version(&self) -> &Version<'a> {
+        &self.0
+    }
+
+    /// Unwraps the version.
+    pub fn into_version(self) -> Version<'a> {
+        self.0
+    }
+}
+
+impl<'a> From<Version<'a>> for Precedence<'a> {
+    fn from(version: Version<'a>) -> Precedence<'a> {
+        Precedence(version)
+    }
+}
+
+impl<'a> Ord for Precedence<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_precedence(&other.0)
+    }
+}
+
+impl<'a> PartialOrd for Precedence<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for Precedence<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_precedence(&other.0)
+    }
+}
+
+impl<'a> Eq for Precedence<'a> {}
+
+impl<'a> Hash for Precedence<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.quad().hash(state);
+        self.0.pre().hash(state);
+    }
+}
+
+impl<'a> fmt::Display for Precedence<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 impl<'a> Ord for Version<'a> {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.quad().cmp(&other.quad()) {
+        match self.cmp_precedence(other) {
             Ordering::Equal => {
-                // handle pre-releases first
-                match (self.pre(), other.pre()) {
-                    (None, Some(_)) => return Ordering::Greater,
-                    (Some(_), None) => return Ordering::Less,
-                    (Some(self_pre), Some(other_pre)) => {
-                        match self_pre.cmp(other_pre) {
-                            Ordering::Equal => {}
-                            other => return other,
-                        };
-                    }
-                    (None, None) => {}
-                }
-
                 // if we have build numbers, compare them
                 if let (Some(self_num), Some(other_num)) =
                     (self.build_number(), other.build_number())
@@ -387,13 +720,194 @@ impl<'a> fmt::Display for Version<'a> {
     }
 }
 
+/// An owned version.
+///
+/// [`Version`] borrows from the string it was parsed from.  This type owns
+/// the version string instead and hands out borrowed versions from it.
+///
+/// ```
+/// use sentry_release_parser::{OwnedVersion, Version};
+///
+/// let owned = OwnedVersion::parse("1.0.0-rc1".to_string()).unwrap();
+/// assert_eq!(owned.as_version().pre(), Some("rc1"));
+/// assert_eq!(owned, OwnedVersion::from(&Version::parse("1.0.0-rc1").unwrap()));
+/// ```
+#[derive(Clone)]
+pub struct OwnedVersion {
+    raw: String,
+    // major, minor, patch, revision, pre, before code and build code one
+    // after another, so that the version does not have to be parsed again
+    parts: String,
+    ends: [usize; 7],
+    components: u8,
+}
+
+impl OwnedVersion {
+    /// Parses an owned version from a string.
+    pub fn parse(version: String) -> Result<OwnedVersion, InvalidVersion> {
+        Ok(OwnedVersion::from(&Version::parse(&version)?))
+    }
+
+    /// Returns the version.
+    pub fn as_version(&self) -> Version<'_> {
+        let part = |idx: usize| {
+            let start = if idx == 0 { 0 } else { self.ends[idx - 1] };
+            &self.parts[start..self.ends[idx]]
+        };
+        Version {
+            raw: &self.raw,
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            revision: part(3),
+            pre: part(4),
+            before_code: part(5),
+            build_code: part(6),
+            components: self.components,
+        }
+    }
+
+    /// Returns the raw version as string.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Converts the owned version into the raw version string.
+    pub fn into_raw(self) -> String {
+        self.raw
+    }
+}
+
+impl<'a> From<&Version<'a>> for OwnedVersion {
+    fn from(version: &Version<'a>) -> OwnedVersion {
+        let mut parts = String::new();
+        let mut ends = [0; 7];
+        for (end, part) in ends.iter_mut().zip([
+            version.major,
+            version.minor,
+            version.patch,
+            version.revision,
+            version.pre,
+            version.before_code,
+            version.build_code,
+        ]) {
+            parts.push_str(part);
+            *end = parts.len();
+        }
+        OwnedVersion {
+            raw: version.raw.to_string(),
+            parts,
+            ends,
+            components: version.components,
+        }
+    }
+}
+
+impl std::str::FromStr for OwnedVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(s: &str) -> Result<OwnedVersion, InvalidVersion> {
+        OwnedVersion::parse(s.to_string())
+    }
+}
+
+impl Ord for OwnedVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_version().cmp(&other.as_version())
+    }
+}
+
+impl PartialOrd for OwnedVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OwnedVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_version() == other.as_version()
+    }
+}
+
+impl Eq for OwnedVersion {}
+
+impl fmt::Debug for OwnedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_version(), f)
+    }
+}
+
+impl fmt::Display for OwnedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// The kind of a release.
+///
+/// This classifies a release by the shape of its version part, which is the
+/// entire release if there is no package.  The kinds are checked in the order
+/// they are declared in, so a timestamp that also parses as version is
+/// classified as timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReleaseKind {
+    /// The version part is a UUID (`8-4-4-4-12` hexadecimal digits).
+    Uuid,
+    /// The version part is a unix timestamp or a date with a time.
+    Timestamp,
+    /// The version part is a build hash.
+    HashOnly,
+    /// A calendar version starting with year and month (`2020.05.1`).
+    #[cfg_attr(feature = "serde", serde(rename = "calver"))]
+    CalVer,
+    /// A version with a build hash as build code.
+    VersionedWithHash,
+    /// A version.
+    Versioned,
+    /// Anything else.
+    Opaque,
+}
+
+impl ReleaseKind {
+    /// Returns the name of the kind as used in the serialized output.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ReleaseKind::Uuid => "uuid",
+            ReleaseKind::Timestamp => "timestamp",
+            ReleaseKind::HashOnly => "hash_only",
+            ReleaseKind::CalVer => "calver",
+            ReleaseKind::VersionedWithHash => "versioned_with_hash",
+            ReleaseKind::Versioned => "versioned",
+            ReleaseKind::Opaque => "opaque",
+        }
+    }
+}
+
+impl fmt::Display for ReleaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// Represents a parsed release.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Releases are ordered so that mixed lists sort stably: releases with a
+/// version come first, followed by releases that are just a build hash and
+/// lastly all other releases.  Within these groups releases are ordered by
+/// package and then by version, build hash or raw version respectively.
+/// Versions are compared by the [`VersionScheme`] that parsed them.  If
+/// releases of a package were parsed by different schemes, they are first
+/// ordered by scheme name.  To only compare the versions of two releases use
+/// [`cmp_version`](Self::cmp_version).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Release<'a> {
     raw: &'a str,
     package: &'a str,
     version_raw: &'a str,
     version: Option<Version<'a>>,
+    scheme: Option<SchemeRef>,
 }
 
 #[cfg(feature = "serde")]
@@ -407,6 +921,7 @@ impl<'a> Serialize for Release<'a> {
         state.serialize_field("version_raw", &self.version_raw())?;
         state.serialize_field("version_parsed", &self.version())?;
         state.serialize_field("build_hash", &self.build_hash())?;
+        state.serialize_field("kind", &self.kind())?;
         state.serialize_field("description", &self.describe().to_string())?;
         state.end()
     }
@@ -414,46 +929,58 @@ impl<'a> Serialize for Release<'a> {
 
 /// Given a string checks if the release is generally valid.
 pub fn validate_release(release: &str) -> Result<(), InvalidRelease> {
-    if release.len() > 200 {
-        Err(InvalidRelease::TooLong)
-    } else if release == "." || release == ".." || release.eq_ignore_ascii_case("latest") {
-        Err(InvalidRelease::RestrictedName)
-    } else if !VALID_API_ATTRIBUTE_REGEX.is_match(release) {
-        Err(InvalidRelease::BadCharacters)
-    } else {
-        Ok(())
-    }
+    validate_release_with(release, &DEFAULT_RELEASE_VALIDATION)
 }
 
 /// Given a string checks if the environment name is generally valid.
 pub fn validate_environment(environment: &str) -> Result<(), InvalidEnvironment> {
-    if environment.len() > 64 {
-        Err(InvalidEnvironment::TooLong)
-    } else if environment == "." || environment == ".." || environment.eq_ignore_ascii_case("none")
-    {
-        Err(InvalidEnvironment::RestrictedName)
-    } else if !VALID_API_ATTRIBUTE_REGEX.is_match(environment) {
-        Err(InvalidEnvironment::BadCharacters)
-    } else {
-        Ok(())
-    }
+    validate_environment_with(environment, &DEFAULT_ENVIRONMENT_VALIDATION)
 }
 
 impl<'a> Release<'a> {
     /// Parses a release from a string.
     pub fn parse(release: &'a str) -> Result<Release<'a>, InvalidRelease> {
+        Release::parse_internal(release, &DEFAULT_RELEASE_VALIDATION, |_, version| {
+            Version::parse(version)
+                .ok()
+                .map(|version| (version, SchemeRef::default_scheme()))
+        })
+    }
+
+    /// Parses a release from a string with the version schemes of a parser.
+    ///
+    /// The version part is parsed by the scheme the parser picks for the
+    /// package.  See [`Parser`] for details.
+    pub fn parse_with(release: &'a str, parser: &Parser) -> Result<Release<'a>, InvalidRelease> {
+        Release::parse_internal(release, parser.validation(), |package, version| {
+            parser.parse_version(package, version)
+        })
+    }
+
+    fn parse_internal<F>(
+        release: &'a str,
+        validation: &ValidationConfig,
+        parse_version: F,
+    ) -> Result<Release<'a>, InvalidRelease>
+    where
+        F: FnOnce(&'a str, &'a str) -> Option<(Version<'a>, SchemeRef)>,
+    {
         let release = release.trim();
-        validate_release(release)?;
+        validate_release_with(release, validation)?;
         if let Some(caps) = RELEASE_REGEX.captures(release) {
             let package = caps.get(1).unwrap().as_str();
             let version_raw = caps.get(2).unwrap().as_str();
             if !is_build_hash(version_raw) {
-                let version = Version::parse(version_raw).ok();
+                let (version, scheme) = match parse_version(package, version_raw) {
+                    Some((version, scheme)) => (Some(version), Some(scheme)),
+                    None => (None, None),
+                };
                 return Ok(Release {
                     raw: release,
                     package,
                     version_raw,
                     version,
+                    scheme,
                 });
             } else {
                 return Ok(Release {
@@ -461,6 +988,7 @@ impl<'a> Release<'a> {
                     package,
                     version_raw,
                     version: None,
+                    scheme: None,
                 });
             }
         }
@@ -469,6 +997,7 @@ impl<'a> Release<'a> {
             package: "",
             version_raw: release,
             version: None,
+            scheme: None,
         })
     }
 
@@ -488,6 +1017,16 @@ impl<'a> Release<'a> {
         }
     }
 
+    /// Returns the package with its structure.
+    pub fn package_info(&self) -> Option<Package<'a>> {
+        self.package().map(Package::parse)
+    }
+
+    /// Separates the build flavor with the default [`FlavorConfig`].
+    pub fn flavored(&self) -> FlavoredRelease<'a> {
+        FlavorConfig::default().detect(self)
+    }
+
     /// The raw version part of the release.
     ///
     /// This is set even if the version part is not a valid version
@@ -501,6 +1040,38 @@ impl<'a> Release<'a> {
         self.version.as_ref()
     }
 
+    /// Returns the name of the version scheme that parsed the version.
+    ///
+    /// This is `"default"` for releases parsed with [`Release::parse`] and
+    /// `None` if the release does not have a version.
+    pub fn scheme(&self) -> Option<&'static str> {
+        self.scheme.as_ref().map(|scheme| scheme.name())
+    }
+
+    /// Returns the version together with the scheme that parsed it.
+    pub(crate) fn version_with_scheme(&self) -> Option<(&Version<'a>, &dyn VersionScheme)> {
+        Some((self.version.as_ref()?, &**self.scheme.as_ref()?))
+    }
+
+    /// Classifies the release.
+    pub fn kind(&self) -> ReleaseKind {
+        if UUID_REGEX.is_match(self.version_raw) {
+            ReleaseKind::Uuid
+        } else if TIMESTAMP_REGEX.is_match(self.version_raw) {
+            ReleaseKind::Timestamp
+        } else if self.version.is_none() && self.build_hash().is_some() {
+            ReleaseKind::HashOnly
+        } else if self.version.is_some() && CALVER_REGEX.is_match(self.version_raw) {
+            ReleaseKind::CalVer
+        } else if self.version.is_some() && self.build_hash().is_some() {
+            ReleaseKind::VersionedWithHash
+        } else if self.version.is_some() {
+            ReleaseKind::Versioned
+        } else {
+            ReleaseKind::Opaque
+        }
+    }
+
     /// Returns the build hash if available.
     pub fn build_hash(&self) -> Option<&'a str> {
         self.version
@@ -524,6 +1095,63 @@ impl<'a> Release<'a> {
     pub fn describe(&self) -> ReleaseDescription<'_> {
         ReleaseDescription(self)
     }
+
+    /// Compares the versions of two releases of the same package.
+    ///
+    /// The versions are compared by the [`VersionScheme`] that parsed them.
+    /// Returns `None` if the releases belong to different packages, if
+    /// either of them does not have a parsed version or if the versions were
+    /// parsed by different schemes.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use sentry_release_parser::Release;
+    ///
+    /// let a = Release::parse("foo@1.0.0").unwrap();
+    /// let b = Release::parse("foo@1.0.0-rc1").unwrap();
+    /// let c = Release::parse("bar@2.0.0").unwrap();
+    /// assert_eq!(a.cmp_version(&b), Some(Ordering::Greater));
+    /// assert_eq!(a.cmp_version(&c), None);
+    /// ```
+    pub fn cmp_version(&self, other: &Release<'_>) -> Option<Ordering> {
+        if self.package != other.package || self.scheme != other.scheme {
+            return None;
+        }
+        let (version, scheme) = self.version_with_scheme()?;
+        Some(scheme.compare(version, other.version()?))
+    }
+
+    /// Describes the change from this release to another release.
+    ///
+    /// This reports whether the package or the build hash changed and, if
+    /// both releases have a version, how the version changed.
+    pub fn diff(&self, other: &Release<'_>) -> ReleaseChange {
+        ReleaseChange::between(self, other)
+    }
+
+    /// Returns the rank of the release within a mixed list.
+    fn sort_group(&self) -> u8 {
+        if self.version.is_some() {
+            0
+        } else if self.build_hash().is_some() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns the columns Sentry stores for this release.
+    pub fn columns(&self) -> ReleaseColumns<'a> {
+        ReleaseColumns::new(self)
+    }
+
+    /// Traces how a release string is parsed and classified.
+    ///
+    /// This is a diagnostic tool which explains for instance why a release
+    /// does not have a version.  It also works for invalid releases.
+    pub fn explain(release: &'a str) -> ReleaseExplanation<'a> {
+        ReleaseExplanation::new(release)
+    }
 }
 
 /// Helper object to format a release into a description.
@@ -553,6 +1181,31 @@ impl<'a> fmt::Display for ReleaseDescription<'a> {
     }
 }
 
+impl<'a> Ord for Release<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_group()
+            .cmp(&other.sort_group())
+            .then_with(|| self.package.cmp(other.package))
+            .then_with(
+                || match (self.version_with_scheme(), other.version_with_scheme()) {
+                    (Some((self_ver, scheme)), Some((other_ver, _))) => self
+                        .scheme
+                        .cmp(&other.scheme)
+                        .then_with(|| scheme.compare(self_ver, other_ver)),
+                    _ => self.build_hash().cmp(&other.build_hash()),
+                },
+            )
+            .then_with(|| self.version_raw.cmp(other.version_raw))
+            .then_with(|| self.raw.cmp(other.raw))
+    }
+}
+
+impl<'a> PartialOrd for Release<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'a> fmt::Display for Release<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut have_package = false;
@@ -585,7 +1238,10 @@ fn test_release_validation() {
     assert_eq!(validate_release(".."), Err(InvalidRelease::RestrictedName));
     assert_eq!(
         validate_release("foo\nbar"),
-        Err(InvalidRelease::BadCharacters)
+        Err(InvalidRelease::BadCharacters {
+            character: '\n',
+            offset: 3
+        })
     );
     assert_eq!(validate_release("good"), Ok(()));
 }
@@ -606,11 +1262,17 @@ fn test_environment_validation() {
     );
     assert_eq!(
         validate_environment("f4f3db928593f258e1d850997be07b577f0779cc5549f9968bae625ea001175bX"),
-        Err(InvalidEnvironment::TooLong)
+        Err(InvalidEnvironment::TooLong {
+            length: 65,
+            limit: 64
+        })
     );
     assert_eq!(
         validate_environment("foo\nbar"),
-        Err(InvalidEnvironment::BadCharacters)
+        Err(InvalidEnvironment::BadCharacters {
+            character: '\n',
+            offset: 3
+        })
     );
     assert_eq!(validate_environment("good"), Ok(()));
 }
@@ -649,3 +1311,43 @@ fn test_version_ordering() {
 
     assert!(ver!("1.0+abcd") < ver!("1.0+abcde"));
 }
+
+#[test]
+fn test_version_precedence() {
+    use std::collections::HashSet;
+
+    macro_rules! ver {
+        ($v:expr) => {
+            Version::parse($v).unwrap()
+        };
+    }
+
+    assert_eq!(
+        ver!("1.0.0+20").cmp_precedence(&ver!("1.0.0+10")),
+        Ordering::Equal
+    );
+    assert!(ver!("1.0.0+20").eq_precedence(&ver!("1.0.0+10")));
+    assert!(ver!("1.0.0+20").eq_precedence(&ver!("1.0")));
+    assert!(!ver!("1.0.0").eq_precedence(&ver!("1.0.0-rc1")));
+    assert_eq!(
+        ver!("1.0.0-rc1+20").cmp_precedence(&ver!("1.0.0+10")),
+        Ordering::Less
+    );
+    assert_eq!(
+        ver!("1.0.0-rc2").cmp_precedence(&ver!("1.0.0-rc1+99")),
+        Ordering::Greater
+    );
+    assert_eq!(
+        ver!("1.0.1+1").cmp_precedence(&ver!("1.0.0+2")),
+        Ordering::Greater
+    );
+
+    assert!(ver!("1.0.0+20").precedence() == ver!("1.0.0+10").precedence());
+    assert!(ver!("1.0.0-rc1").precedence() < ver!("1.0.0+10").precedence());
+
+    let set: HashSet<_> = ["1.0.0+1", "1.0.0+2", "1.0", "1.0.0-rc1", "2.0"]
+        .iter()
+        .map(|v| ver!(v).precedence())
+        .collect();
+    assert_eq!(set.len(), 3);
+}
@@ -15,6 +15,11 @@ const VERSION_REGEX = new RegExp(
 $`.replace(/\s/g, "")
 );
 const HEX_REGEX = /^[a-fA-F0-9]+$/;
+const UUID_REGEX =
+  /^[a-fA-F0-9]{8}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{4}-[a-fA-F0-9]{12}$/;
+const TIMESTAMP_REGEX =
+  /^(?:[0-9]{10}|[0-9]{13}|[0-9]{14}|[0-9]{4}[-.]?[0-9]{2}[-.]?[0-9]{2}[T_.-][0-9]{2}[:.-]?[0-9]{2}(?:[:.-]?[0-9]{2})?Z?)$/;
+const CALVER_REGEX = /^(?:19[7-9][0-9]|2[0-9]{3})\.(?:0?[1-9]|1[0-2])(?:[.+-].*)?$/;
 const VALID_RELEASE_REGEX = /^[^/\r\n]*$/;
 
 export class InvalidRelease extends Error {
@@ -83,6 +88,25 @@ export class Release {
     return null;
   }
 
+  getKind(): string {
+    const hash = this.getBuildHash();
+    if (this.versionRaw.match(UUID_REGEX)) {
+      return "uuid";
+    } else if (this.versionRaw.match(TIMESTAMP_REGEX)) {
+      return "timestamp";
+    } else if (!this.versionParsed && hash) {
+      return "hash_only";
+    } else if (this.versionParsed && this.versionRaw.match(CALVER_REGEX)) {
+      return "calver";
+    } else if (this.versionParsed && hash) {
+      return "versioned_with_hash";
+    } else if (this.versionParsed) {
+      return "versioned";
+    } else {
+      return "opaque";
+    }
+  }
+
   describe(): string {
     const hash = this.getBuildHash();
     const shortHash = (hash && hash.substr(0, 12)) || null;
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::glob::glob_match;
+use crate::parser::{OwnedVersion, Release, Version};
+
+/// An error indicating an invalid release pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidReleasePattern;
+
+impl std::error::Error for InvalidReleasePattern {}
+
+impl fmt::Display for InvalidReleasePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid release pattern")
+    }
+}
+
+/// Splits an optional package off a `package@version` value.
+///
+/// This follows the rules of [`Release::parse`] where a leading `@` is part
+/// of the package.
+pub(crate) fn split_package(value: &str) -> (Option<&str>, &str) {
+    let offset = if value.starts_with('@') { 1 } else { 0 };
+    match value[offset..].find('@') {
+        Some(idx) => (Some(&value[..offset + idx]), &value[offset + idx + 1..]),
+        None => (None, value),
+    }
+}
+
+/// Parses a version prefix ending in `.*` such as `1.2.*`.
+///
+/// Returns the version without the wildcard and the number of components
+/// that need to match.
+pub(crate) fn parse_component_wildcard(version: &str) -> Option<(&str, usize)> {
+    let prefix = version.strip_suffix(".*")?;
+    let components = prefix.split('.').count();
+    if components > 3
+        || prefix.split('.').any(|c| c.is_empty())
+        || !prefix.bytes().all(|c| c.is_ascii_digit() || c == b'.')
+    {
+        return None;
+    }
+    Some((prefix, components))
+}
+
+/// Returns the version components as an array.
+pub(crate) fn quad_array(version: &Version<'_>) -> [u64; 4] {
+    let (major, minor, patch, revision) = version.quad();
+    [major, minor, patch, revision]
+}
+
+/// Checks if the build hash of a release starts with a lowercase prefix.
+fn matches_hash_prefix(prefix: &str, release: &Release<'_>) -> bool {
+    match release.build_hash() {
+        Some(hash) => hash.to_ascii_lowercase().starts_with(prefix),
+        None => release.version_raw().eq_ignore_ascii_case(prefix),
+    }
+}
+
+#[derive(Debug, Clone)]
+enum VersionPattern {
+    Any,
+    Components([u64; 4], usize),
+    Exact(OwnedVersion, Option<String>),
+    HashPrefix(String),
+    Glob(String),
+}
+
+/// A pattern matching releases.
+///
+/// Patterns are written like releases, with an optional package and a
+/// version part.  Patterns without a package match releases of any package.
+///
+/// - the package can contain `*` wildcards: `com.acme.*@1.0`.
+/// - the version is matched by component if it ends in `.*`.  `1.2.*`
+///   matches `1.2`, `1.2.0` and `1.2.10-rc1` but not `1.20.0`.
+/// - a valid version matches releases of the same version.  The build code
+///   is only compared if the pattern has one.
+/// - hexadecimal values match build hashes by prefix: `*@a86d127c`.
+///   Releases without a build hash match if their version is the value.
+///   Values with at least 7 digits that are also valid versions, such as
+///   `*@0852407`, match both ways.
+/// - everything else is matched as glob against the raw version.
+///
+/// ```
+/// use sentry_release_parser::{Release, ReleasePattern};
+///
+/// let pattern = ReleasePattern::parse("com.acme.*@1.4.*").unwrap();
+/// assert!(pattern.matches(&Release::parse("com.acme.app@1.4.2").unwrap()));
+/// assert!(!pattern.matches(&Release::parse("com.acme.app@1.40.0").unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReleasePattern {
+    raw: String,
+    package: Option<String>,
+    version: VersionPattern,
+}
+
+impl ReleasePattern {
+    /// Parses a release pattern.
+    pub fn parse(pattern: &str) -> Result<ReleasePattern, InvalidReleasePattern> {
+        let pattern = pattern.trim();
+        let (package, version) = split_package(pattern);
+        if version.is_empty() || package.is_some_and(|p| p.is_empty()) {
+            return Err(InvalidReleasePattern);
+        }
+
+        let version = if version == "*" {
+            VersionPattern::Any
+        } else if let Some((prefix, components)) = parse_component_wildcard(version) {
+            let prefix = Version::parse(prefix).map_err(|_| InvalidReleasePattern)?;
+            VersionPattern::Components(quad_array(&prefix), components)
+        } else if let Ok(expected) = OwnedVersion::parse(version.to_string()) {
+            // all digit hash prefixes such as `0852407` are valid versions too
+            let hash_prefix = Some(version)
+                .filter(|v| v.len() >= 7 && v.bytes().all(|c| c.is_ascii_hexdigit()))
+                .map(str::to_ascii_lowercase);
+            VersionPattern::Exact(expected, hash_prefix)
+        } else if version.bytes().all(|c| c.is_ascii_hexdigit()) {
+            VersionPattern::HashPrefix(version.to_ascii_lowercase())
+        } else {
+            VersionPattern::Glob(version.to_string())
+        };
+
+        Ok(ReleasePattern {
+            raw: pattern.to_string(),
+            package: package.filter(|&p| p != "*").map(str::to_string),
+            version,
+        })
+    }
+
+    /// Returns the pattern as it was given.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the package pattern if the pattern is restricted to packages.
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    /// Checks if the release matches the pattern.
+    pub fn matches(&self, release: &Release<'_>) -> bool {
+        if let Some(ref package) = self.package {
+            if !release.package().is_some_and(|p| glob_match(package, p)) {
+                return false;
+            }
+        }
+
+        match self.version {
+            VersionPattern::Any => true,
+            VersionPattern::Components(ref prefix, components) => release
+                .version()
+                .is_some_and(|v| quad_array(v)[..components] == prefix[..components]),
+            VersionPattern::Exact(ref expected, ref hash_prefix) => {
+                let expected = expected.as_version();
+                release.version().is_some_and(|v| {
+                    v.quad() == expected.quad()
+                        && v.pre() == expected.pre()
+                        && (expected.build_code().is_none()
+                            || v.build_code() == expected.build_code())
+                }) || hash_prefix
+                    .as_ref()
+                    .is_some_and(|prefix| matches_hash_prefix(prefix, release))
+            }
+            VersionPattern::HashPrefix(ref prefix) => matches_hash_prefix(prefix, release),
+            VersionPattern::Glob(ref pattern) => glob_match(pattern, release.version_raw()),
+        }
+    }
+}
+
+impl FromStr for ReleasePattern {
+    type Err = InvalidReleasePattern;
+
+    fn from_str(s: &str) -> Result<ReleasePattern, InvalidReleasePattern> {
+        ReleasePattern::parse(s)
+    }
+}
+
+impl fmt::Display for ReleasePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
@@ -0,0 +1,497 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::parser::Version;
+use crate::scheme::VersionScheme;
+
+lazy_static! {
+    pub(crate) static ref PEP440_REGEX: Regex = Regex::new(
+        r"(?xi-u)
+        ^
+            v?
+            (?:(?P<epoch>[0-9]+)!)?
+            (?P<release>[0-9]+(?:\.[0-9]+)*)
+            (?P<suffix>
+            (?:
+                [-_.]?
+                (?P<pre_l>alpha|a|beta|b|preview|pre|c|rc)
+                [-_.]?
+                (?P<pre_n>[0-9]+)?
+            )?
+            (?:
+                (?:-(?P<post_n1>[0-9]+))
+                |
+                (?:[-_.]?(?P<post_l>post|rev|r)[-_.]?(?P<post_n2>[0-9]+)?)
+            )?
+            (?:[-_.]?(?P<dev_l>dev)[-_.]?(?P<dev_n>[0-9]+)?)?
+            )
+            (?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?
+        $
+        "
+    )
+    .unwrap();
+    pub(crate) static ref SPECIFIER_REGEX: Regex =
+        Regex::new(r"^[ \t]*(~=|===|==|!=|<=|>=|<|>)[ \t]*([^ \t]+?)[ \t]*$").unwrap();
+}
+
+/// An error indicating an invalid PEP 440 specifier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidSpecifier;
+
+impl std::error::Error for InvalidSpecifier {}
+
+impl fmt::Display for InvalidSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid specifier")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum PreKind {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey {
+    /// A development release without pre or post release sorts before all
+    /// pre-releases.
+    DevOnly,
+    Pre(PreKind, u64),
+    Final,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalPart {
+    AlphaNumeric(String),
+    Numeric(u64),
+}
+
+/// A version as defined by PEP 440.
+#[derive(Debug, Clone)]
+pub(crate) struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(PreKind, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Option<Vec<LocalPart>>,
+}
+
+impl Pep440Version {
+    pub(crate) fn parse(version: &str) -> Option<Pep440Version> {
+        let caps = PEP440_REGEX.captures(version.trim())?;
+        let num = |name| -> Option<Option<u64>> {
+            match caps.name(name) {
+                Some(m) => m.as_str().parse().ok().map(Some),
+                None => Some(None),
+            }
+        };
+
+        let pre = match caps.name("pre_l") {
+            Some(label) => {
+                let kind = match label.as_str().to_ascii_lowercase().as_str() {
+                    "a" | "alpha" => PreKind::Alpha,
+                    "b" | "beta" => PreKind::Beta,
+                    _ => PreKind::Rc,
+                };
+                Some((kind, num("pre_n")?.unwrap_or(0)))
+            }
+            None => None,
+        };
+        let post = match (num("post_n1")?, caps.name("post_l")) {
+            (Some(n), _) => Some(n),
+            (None, Some(_)) => Some(num("post_n2")?.unwrap_or(0)),
+            (None, None) => None,
+        };
+        let dev = match caps.name("dev_l") {
+            Some(_) => Some(num("dev_n")?.unwrap_or(0)),
+            None => None,
+        };
+        let local = caps.name("local").map(|local| {
+            local
+                .as_str()
+                .split(&['-', '_', '.'][..])
+                .map(|part| match part.parse() {
+                    Ok(num) if part.bytes().all(|c| c.is_ascii_digit()) => LocalPart::Numeric(num),
+                    _ => LocalPart::AlphaNumeric(part.to_ascii_lowercase()),
+                })
+                .collect()
+        });
+
+        Some(Pep440Version {
+            epoch: num("epoch")?.unwrap_or(0),
+            release: caps["release"]
+                .split('.')
+                .map(|x| x.parse().ok())
+                .collect::<Option<_>>()?,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+
+    pub(crate) fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// Returns the release segment without trailing zeroes.
+    fn release_key(&self) -> &[u64] {
+        let len = self
+            .release
+            .iter()
+            .rposition(|&x| x != 0)
+            .map_or(0, |idx| idx + 1);
+        &self.release[..len]
+    }
+
+    fn pre_key(&self) -> PreKey {
+        match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => PreKey::DevOnly,
+            (None, _, _) => PreKey::Final,
+            (Some((kind, num)), _, _) => PreKey::Pre(kind, num),
+        }
+    }
+
+    /// Compares two versions while ignoring local version labels.
+    fn cmp_public(&self, other: &Pep440Version) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| self.release_key().cmp(other.release_key()))
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| (self.dev.is_none(), self.dev).cmp(&(other.dev.is_none(), other.dev)))
+    }
+
+    fn eq_base(&self, other: &Pep440Version) -> bool {
+        self.epoch == other.epoch && self.release_key() == other.release_key()
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_public(other)
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440Version {}
+
+/// Version scheme following PEP 440.
+///
+/// This accepts all PEP 440 versions with up to four release components,
+/// including post releases (`1.0.post1`) and development releases
+/// (`1.0.dev2`) that [`Version::parse`] does not understand.  Versions
+/// compare as defined by PEP 440.  In the version view the pre-release and
+/// development segments are the pre-release and the local version label is
+/// the build code.  Post releases sort after their final release, so for
+/// them only the pre-release segment is kept (`1.0rc1.post2` has the
+/// pre-release `rc1` and `1.0.post1` none).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pep440Scheme;
+
+impl VersionScheme for Pep440Scheme {
+    fn name(&self) -> &'static str {
+        "pep440"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        let caps = PEP440_REGEX.captures(version)?;
+        Pep440Version::parse(version)?;
+        let mut release = caps.name("release")?.as_str().split('.');
+        let quad = (
+            release.next()?,
+            release.next(),
+            release.next(),
+            release.next(),
+        );
+        if release.next().is_some() {
+            return None;
+        }
+        let pre = if caps.name("post_n1").is_some() || caps.name("post_l").is_some() {
+            caps.name("pre_l").map(|label| {
+                let end = caps.name("pre_n").map_or(label.end(), |num| num.end());
+                &version[label.start()..end]
+            })
+        } else {
+            caps.name("suffix")
+                .map(|x| x.as_str().trim_start_matches(&['-', '_', '.'][..]))
+                .filter(|x| !x.is_empty())
+        };
+        // the version view cannot hold `_` separators, so only the label
+        // marks such pre-releases and such local labels are left out
+        let label = caps.name("pre_l").or_else(|| caps.name("dev_l"));
+        let pre = match (pre, label) {
+            (Some(pre), Some(label)) if pre.contains('_') => Some(label.as_str()),
+            (pre, _) => pre,
+        };
+        let local = caps
+            .name("local")
+            .map(|x| x.as_str())
+            .filter(|x| !x.contains('_'));
+        Version::from_parts(version, quad, pre, local).ok()
+    }
+
+    fn compare(&self, a: &Version<'_>, b: &Version<'_>) -> Ordering {
+        match (Pep440Version::parse(a.raw()), Pep440Version::parse(b.raw())) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Compatible,
+    Equal,
+    NotEqual,
+    LessEqual,
+    GreaterEqual,
+    Less,
+    Greater,
+    Arbitrary,
+}
+
+#[derive(Debug, Clone)]
+struct Specifier {
+    op: Op,
+    raw: String,
+    version: Option<Pep440Version>,
+    wildcard: bool,
+}
+
+impl Specifier {
+    fn parse(s: &str) -> Result<Specifier, InvalidSpecifier> {
+        let caps = SPECIFIER_REGEX.captures(s).ok_or(InvalidSpecifier)?;
+        let op = match &caps[1] {
+            "~=" => Op::Compatible,
+            "==" => Op::Equal,
+            "!=" => Op::NotEqual,
+            "<=" => Op::LessEqual,
+            ">=" => Op::GreaterEqual,
+            "<" => Op::Less,
+            ">" => Op::Greater,
+            _ => Op::Arbitrary,
+        };
+        let raw = caps[2].to_string();
+        if op == Op::Arbitrary {
+            return Ok(Specifier {
+                op,
+                raw,
+                version: None,
+                wildcard: false,
+            });
+        }
+
+        let (version_raw, wildcard) = match raw.strip_suffix(".*") {
+            Some(prefix) => (prefix, true),
+            None => (raw.as_str(), false),
+        };
+        let version = Pep440Version::parse(version_raw).ok_or(InvalidSpecifier)?;
+        let valid = match op {
+            Op::Equal | Op::NotEqual => {
+                !wildcard
+                    || (version.pre.is_none()
+                        && version.post.is_none()
+                        && version.dev.is_none()
+                        && version.local.is_none())
+            }
+            Op::Compatible => !wildcard && version.local.is_none() && version.release.len() >= 2,
+            _ => !wildcard && version.local.is_none(),
+        };
+        if !valid {
+            return Err(InvalidSpecifier);
+        }
+
+        Ok(Specifier {
+            op,
+            raw,
+            version: Some(version),
+            wildcard,
+        })
+    }
+
+    /// Returns `true` if the specifier explicitly asks for pre-releases.
+    fn wants_prereleases(&self) -> bool {
+        match self.op {
+            Op::Equal | Op::GreaterEqual | Op::LessEqual | Op::Compatible => {
+                self.version.as_ref().is_some_and(|v| v.is_prerelease())
+            }
+            Op::Arbitrary => Pep440Version::parse(&self.raw).is_some_and(|v| v.is_prerelease()),
+            _ => false,
+        }
+    }
+
+    fn matches(&self, raw: &str, prospective: Option<&Pep440Version>) -> bool {
+        let (spec, prospective) = match (self.op, &self.version, prospective) {
+            (Op::Arbitrary, _, _) => return raw.eq_ignore_ascii_case(&self.raw),
+            (_, Some(spec), Some(prospective)) => (spec, prospective),
+            _ => return false,
+        };
+
+        match self.op {
+            Op::Compatible => {
+                let prefix = &spec.release[..spec.release.len() - 1];
+                prospective.cmp_public(spec) != Ordering::Less
+                    && prefix_match(spec.epoch, prefix, prospective)
+            }
+            Op::Equal => equal(spec, self.wildcard, prospective),
+            Op::NotEqual => !equal(spec, self.wildcard, prospective),
+            Op::LessEqual => prospective.cmp_public(spec) != Ordering::Greater,
+            Op::GreaterEqual => prospective.cmp_public(spec) != Ordering::Less,
+            Op::Less => {
+                prospective.cmp_public(spec) == Ordering::Less
+                    && !(!spec.is_prerelease()
+                        && prospective.is_prerelease()
+                        && prospective.eq_base(spec))
+            }
+            Op::Greater => {
+                prospective.cmp(spec) == Ordering::Greater
+                    && !(!spec.is_postrelease()
+                        && prospective.is_postrelease()
+                        && prospective.eq_base(spec))
+                    && !(prospective.local.is_some() && prospective.eq_base(spec))
+            }
+            Op::Arbitrary => unreachable!(),
+        }
+    }
+}
+
+fn prefix_match(epoch: u64, prefix: &[u64], prospective: &Pep440Version) -> bool {
+    epoch == prospective.epoch
+        && prefix
+            .iter()
+            .enumerate()
+            .all(|(idx, num)| prospective.release.get(idx).copied().unwrap_or(0) == *num)
+}
+
+fn equal(spec: &Pep440Version, wildcard: bool, prospective: &Pep440Version) -> bool {
+    if wildcard {
+        prefix_match(spec.epoch, &spec.release, prospective)
+    } else if spec.local.is_none() {
+        prospective.cmp_public(spec) == Ordering::Equal
+    } else {
+        prospective == spec
+    }
+}
+
+/// A set of PEP 440 version specifiers.
+///
+/// This parses comma separated specifiers such as `~=1.4.2` or
+/// `>=1.0,!=1.3.*,<2` and matches them against versions with the semantics
+/// of PEP 440, including prefix matching (`==1.2.*`), compatible releases
+/// (`~=`) and arbitrary equality (`===`).
+///
+/// Versions are interpreted by parsing their raw string as PEP 440 version,
+/// so `1.0rc1` is a release candidate, `1.0dev2` a development release
+/// and `1.0-1` a post release.  The build code becomes the local version
+/// label.  Versions that are not valid PEP 440 versions only match
+/// arbitrary equality.
+///
+/// Pre-releases are excluded unless one of the specifiers names a
+/// pre-release or they are explicitly allowed with
+/// [`with_prereleases`](Self::with_prereleases).
+///
+/// ```
+/// use sentry_release_parser::{SpecifierSet, Version};
+///
+/// let specifiers = SpecifierSet::parse(">=1.0,!=1.3.*,<2").unwrap();
+/// assert!(specifiers.matches(&Version::parse("1.4.2").unwrap()));
+/// assert!(!specifiers.matches(&Version::parse("1.3.1").unwrap()));
+/// assert!(!specifiers.matches(&Version::parse("1.5rc1").unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpecifierSet {
+    specifiers: Vec<Specifier>,
+    prereleases: Option<bool>,
+}
+
+impl SpecifierSet {
+    /// Parses a comma separated list of specifiers.
+    pub fn parse(specifiers: &str) -> Result<SpecifierSet, InvalidSpecifier> {
+        Ok(SpecifierSet {
+            specifiers: specifiers
+                .split(',')
+                .filter(|x| !x.trim().is_empty())
+                .map(Specifier::parse)
+                .collect::<Result<_, _>>()?,
+            prereleases: None,
+        })
+    }
+
+    /// Overrides whether pre-releases can match.
+    pub fn with_prereleases(mut self, allow: bool) -> SpecifierSet {
+        self.prereleases = Some(allow);
+        self
+    }
+
+    /// Checks if the version matches all specifiers.
+    pub fn matches(&self, version: &Version<'_>) -> bool {
+        self.matches_str(version.raw())
+    }
+
+    pub(crate) fn matches_str(&self, raw: &str) -> bool {
+        let prospective = Pep440Version::parse(raw);
+        let prereleases = self
+            .prereleases
+            .unwrap_or_else(|| self.specifiers.iter().any(|s| s.wants_prereleases()));
+        if !prereleases && prospective.as_ref().is_some_and(|v| v.is_prerelease()) {
+            return false;
+        }
+        self.specifiers
+            .iter()
+            .all(|s| s.matches(raw, prospective.as_ref()))
+    }
+}
+
+impl FromStr for SpecifierSet {
+    type Err = InvalidSpecifier;
+
+    fn from_str(s: &str) -> Result<SpecifierSet, InvalidSpecifier> {
+        SpecifierSet::parse(s)
+    }
+}
+
+impl fmt::Display for SpecifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, specifier) in self.specifiers.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            let op = match specifier.op {
+                Op::Compatible => "~=",
+                Op::Equal => "==",
+                Op::NotEqual => "!=",
+                Op::LessEqual => "<=",
+                Op::GreaterEqual => ">=",
+                Op::Less => "<",
+                Op::Greater => ">",
+                Op::Arbitrary => "===",
+            };
+            write!(f, "{}{}", op, specifier.raw)?;
+        }
+        Ok(())
+    }
+}
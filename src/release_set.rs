@@ -0,0 +1,225 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds};
+
+use crate::parser::{Release, Version};
+#[cfg(doc)]
+use crate::scheme::VersionScheme;
+
+/// Compares the version of a release in a package bucket to a version.
+///
+/// The version is compared with the scheme of the release.
+fn cmp_to(release: &Release<'_>, version: &Version<'_>) -> Ordering {
+    release
+        .version_with_scheme()
+        .map_or(Ordering::Less, |(v, scheme)| scheme.compare(v, version))
+}
+
+/// Splits the sorted releases of a package into runs of the same scheme.
+fn scheme_groups<'r, 'a>(releases: &'r [Release<'a>]) -> impl Iterator<Item = &'r [Release<'a>]> {
+    let mut rest = releases;
+    std::iter::from_fn(move || {
+        let scheme = rest.first()?.scheme();
+        let (group, tail) = rest.split_at(rest.partition_point(|r| r.scheme() == scheme));
+        rest = tail;
+        Some(group)
+    })
+}
+
+/// A sorted collection of releases.
+///
+/// Releases with a version are indexed by package and kept sorted by
+/// version.  Releases without a version (build hashes and other opaque
+/// release names) are kept in a separate bucket.  Iteration follows the
+/// order of [`Release`], so versions are compared by the [`VersionScheme`]
+/// that parsed them.  Releases of a package parsed by different schemes are
+/// grouped by scheme.
+///
+/// ```
+/// use sentry_release_parser::{Release, ReleaseSet, Version};
+///
+/// let set: ReleaseSet = ["foo@1.0.0", "foo@2.0.0-rc1", "foo@1.5.0", "bar@3.0"]
+///     .iter()
+///     .map(|r| Release::parse(r).unwrap())
+///     .collect();
+///
+/// assert_eq!(set.latest("foo", false).unwrap().raw(), "foo@1.5.0");
+/// assert_eq!(set.latest("foo", true).unwrap().raw(), "foo@2.0.0-rc1");
+///
+/// let lower = Version::parse("1.0").unwrap();
+/// let upper = Version::parse("2.0").unwrap();
+/// let matches: Vec<_> = set.range("foo", lower..upper).map(|r| r.raw()).collect();
+/// assert_eq!(matches, ["foo@1.0.0", "foo@1.5.0", "foo@2.0.0-rc1"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseSet<'a> {
+    packages: BTreeMap<&'a str, Vec<Release<'a>>>,
+    unversioned: Vec<Release<'a>>,
+}
+
+impl<'a> ReleaseSet<'a> {
+    /// Creates an empty release set.
+    pub fn new() -> ReleaseSet<'a> {
+        ReleaseSet::default()
+    }
+
+    /// Adds a release to the set.
+    ///
+    /// Returns `false` if the release was already contained in the set.
+    pub fn insert(&mut self, release: Release<'a>) -> bool {
+        let bucket = match release.version() {
+            // releases only have a version if they also have a package
+            Some(_) => self
+                .packages
+                .entry(release.package().unwrap_or_default())
+                .or_default(),
+            None => &mut self.unversioned,
+        };
+        match bucket.binary_search(&release) {
+            Ok(_) => false,
+            Err(idx) => {
+                bucket.insert(idx, release);
+                true
+            }
+        }
+    }
+
+    /// Checks if the set contains a release.
+    pub fn contains(&self, release: &Release<'_>) -> bool {
+        let bucket = match release.version() {
+            Some(_) => self.releases(release.package().unwrap_or_default()),
+            None => &self.unversioned,
+        };
+        bucket.binary_search_by(|r| r.cmp(release)).is_ok()
+    }
+
+    /// Returns the number of releases in the set.
+    pub fn len(&self) -> usize {
+        self.packages.values().map(Vec::len).sum::<usize>() + self.unversioned.len()
+    }
+
+    /// Returns `true` if the set does not contain any releases.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over all packages that have releases with a version.
+    pub fn packages(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.packages.keys().copied()
+    }
+
+    /// Returns the releases of a package sorted by version.
+    pub fn releases(&self, package: &str) -> &[Release<'a>] {
+        self.packages.get(package).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns all releases without a version.
+    ///
+    /// These are build hashes followed by all other unversioned releases.
+    pub fn unversioned(&self) -> &[Release<'a>] {
+        &self.unversioned
+    }
+
+    /// Iterates over all releases in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Release<'a>> + '_ {
+        self.packages
+            .values()
+            .flat_map(|releases| releases.iter())
+            .chain(self.unversioned.iter())
+    }
+
+    /// Returns the latest release of a package.
+    ///
+    /// Pre-releases are only considered if `include_prereleases` is set.  If
+    /// the releases of the package were parsed by different schemes, the
+    /// latest releases of each scheme are compared with `Version::cmp`.
+    pub fn latest(&self, package: &str, include_prereleases: bool) -> Option<&Release<'a>> {
+        scheme_groups(self.releases(package))
+            .filter_map(|releases| {
+                releases.iter().rev().find(|release| {
+                    include_prereleases || release.version().is_some_and(|v| v.pre().is_none())
+                })
+            })
+            .max_by(|a, b| a.version().cmp(&b.version()))
+    }
+
+    /// Iterates over the releases of a package with a version in the range.
+    ///
+    /// The bounds are compared with the scheme of each release.
+    pub fn range<'b, R>(&self, package: &str, range: R) -> impl Iterator<Item = &Release<'a>> + '_
+    where
+        R: RangeBounds<Version<'b>>,
+    {
+        let matches: Vec<_> = scheme_groups(self.releases(package))
+            .map(|releases| {
+                let start = match range.start_bound() {
+                    Bound::Included(bound) => {
+                        releases.partition_point(|r| cmp_to(r, bound).is_lt())
+                    }
+                    Bound::Excluded(bound) => {
+                        releases.partition_point(|r| cmp_to(r, bound).is_le())
+                    }
+                    Bound::Unbounded => 0,
+                };
+                let end = match range.end_bound() {
+                    Bound::Included(bound) => {
+                        releases.partition_point(|r| cmp_to(r, bound).is_le())
+                    }
+                    Bound::Excluded(bound) => {
+                        releases.partition_point(|r| cmp_to(r, bound).is_lt())
+                    }
+                    Bound::Unbounded => releases.len(),
+                };
+                &releases[start..end.max(start)]
+            })
+            .collect();
+        matches.into_iter().flatten()
+    }
+
+    /// Returns the releases of a package parsed by the scheme of a release.
+    fn same_scheme(&self, release: &Release<'_>) -> Option<&[Release<'a>]> {
+        scheme_groups(self.releases(release.package()?))
+            .find(|releases| releases[0].scheme() == release.scheme())
+    }
+
+    /// Returns the release of the same package preceding the given release.
+    ///
+    /// Only releases parsed by the same scheme are considered.  The given
+    /// release does not need to be contained in the set.  Returns `None` if
+    /// the release does not have a version.
+    pub fn previous(&self, release: &Release<'_>) -> Option<&Release<'a>> {
+        let version = release.version()?;
+        let releases = self.same_scheme(release)?;
+        let idx = releases.partition_point(|r| cmp_to(r, version).is_lt());
+        idx.checked_sub(1).map(|idx| &releases[idx])
+    }
+
+    /// Returns the release of the same package following the given release.
+    ///
+    /// Only releases parsed by the same scheme are considered.  The given
+    /// release does not need to be contained in the set.  Returns `None` if
+    /// the release does not have a version.
+    pub fn next(&self, release: &Release<'_>) -> Option<&Release<'a>> {
+        let version = release.version()?;
+        let releases = self.same_scheme(release)?;
+        let idx = releases.partition_point(|r| cmp_to(r, version).is_le());
+        releases.get(idx)
+    }
+}
+
+impl<'a> Extend<Release<'a>> for ReleaseSet<'a> {
+    fn extend<I: IntoIterator<Item = Release<'a>>>(&mut self, iter: I) {
+        for release in iter {
+            self.insert(release);
+        }
+    }
+}
+
+impl<'a> FromIterator<Release<'a>> for ReleaseSet<'a> {
+    fn from_iter<I: IntoIterator<Item = Release<'a>>>(iter: I) -> ReleaseSet<'a> {
+        let mut set = ReleaseSet::new();
+        set.extend(iter);
+        set
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+
+use crate::parser::is_build_hash;
+use crate::validation::{
+    LengthUnit, ValidationConfig, DEFAULT_ENVIRONMENT_VALIDATION, DEFAULT_RELEASE_VALIDATION,
+};
+
+/// The character invalid separators are replaced with.
+const SEPARATOR_REPLACEMENT: char = '-';
+
+/// The prefix added to restricted names.
+const RESTRICTED_PREFIX: &str = "_";
+
+/// A change made while sanitizing a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SanitizeChange {
+    /// Leading or trailing whitespace was removed.
+    Trimmed,
+    /// A character was replaced.
+    ///
+    /// `/` and `\` are replaced with `-` and whitespace control characters
+    /// with a space.
+    ReplacedCharacter {
+        /// The invalid character.
+        character: char,
+        /// The replacement.
+        replacement: char,
+        /// The byte offset of the character in the input.
+        offset: usize,
+    },
+    /// An invalid character was removed.
+    RemovedCharacter {
+        /// The invalid character.
+        character: char,
+        /// The byte offset of the character in the input.
+        offset: usize,
+    },
+    /// The value was normalized to normalization form C.
+    Normalized,
+    /// The value was a restricted name and was prefixed with `_`.
+    RenamedRestricted {
+        /// The restricted name.
+        name: String,
+    },
+    /// The value was too long and was truncated.
+    Truncated {
+        /// The length before truncation.
+        length: usize,
+        /// The maximum allowed length.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for SanitizeChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SanitizeChange::Trimmed => write!(f, "trimmed whitespace"),
+            SanitizeChange::ReplacedCharacter {
+                character,
+                replacement,
+                offset,
+            } => write!(
+                f,
+                "replaced {:?} at position {} with {:?}",
+                character, offset, replacement
+            ),
+            SanitizeChange::Normalized => write!(f, "normalized to NFC"),
+            SanitizeChange::RemovedCharacter { character, offset } => {
+                write!(f, "removed {:?} at position {}", character, offset)
+            }
+            SanitizeChange::RenamedRestricted { ref name } => {
+                write!(f, "renamed restricted name {:?}", name)
+            }
+            SanitizeChange::Truncated { length, limit } => {
+                write!(f, "truncated from length {} to {}", length, limit)
+            }
+        }
+    }
+}
+
+/// A sanitized value with the changes that were made.
+///
+/// ```
+/// use sentry_release_parser::{sanitize_release, Release};
+///
+/// let sanitized = sanitize_release("my/app@1.0\n");
+/// assert_eq!(sanitized.value(), "my-app@1.0");
+/// assert_eq!(sanitized.changes().len(), 2);
+/// assert!(Release::parse(sanitized.value()).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    value: String,
+    changes: Vec<SanitizeChange>,
+}
+
+impl Sanitized {
+    /// Returns the sanitized value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the changes that were made.
+    pub fn changes(&self) -> &[SanitizeChange] {
+        &self.changes
+    }
+
+    /// Returns `true` if the value had to be changed.
+    pub fn is_changed(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Converts the result into the sanitized value.
+    pub fn into_value(self) -> String {
+        self.value
+    }
+}
+
+impl fmt::Display for Sanitized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Returns the longest prefix of a value that fits into a length limit.
+fn truncate(value: &str, limit: usize, unit: LengthUnit) -> &str {
+    let end = match unit {
+        LengthUnit::Bytes => {
+            let mut end = limit.min(value.len());
+            while !value.is_char_boundary(end) {
+                end -= 1;
+            }
+            end
+        }
+        LengthUnit::Chars => value
+            .char_indices()
+            .nth(limit)
+            .map_or(value.len(), |(idx, _)| idx),
+    };
+    &value[..end]
+}
+
+/// Truncates a release while keeping the `package@version` structure and a
+/// trailing build hash where possible.
+fn truncate_release(release: &str, config: &ValidationConfig) -> String {
+    let limit = config.max_length();
+    let unit = config.length_unit();
+    let split = release
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '@')
+        .map(|(idx, _)| (&release[..idx], &release[idx..]));
+
+    if let Some((package, version)) = split {
+        // shorten the package if the version fits on its own
+        if let Some(available) = limit.checked_sub(config.length_of(version)) {
+            let package = truncate(package, available, unit);
+            if !package.is_empty() {
+                return format!("{}{}", package, version);
+            }
+        }
+
+        // otherwise shorten the version but keep a build hash
+        let prefix = format!("{}@", package);
+        let (before_code, build) = match version[1..].rfind('+') {
+            Some(idx) if is_build_hash(&version[idx + 2..]) => {
+                (&version[1..idx + 1], &version[idx + 1..])
+            }
+            _ => (&version[1..], ""),
+        };
+        let used = config.length_of(&prefix) + config.length_of(build);
+        if let Some(available) = limit.checked_sub(used) {
+            let before_code = truncate(before_code, available, unit);
+            if !before_code.is_empty() {
+                return format!("{}{}{}", prefix, before_code, build);
+            }
+        }
+    }
+
+    truncate(release, limit, unit).to_string()
+}
+
+/// Sanitizes a value against validation rules.
+fn sanitize(
+    value: &str,
+    config: &ValidationConfig,
+    truncate_value: fn(&str, &ValidationConfig) -> String,
+) -> Sanitized {
+    let mut changes = vec![];
+    let trimmed = value.trim();
+    let start = trimmed.as_ptr() as usize - value.as_ptr() as usize;
+
+    let policy = config.character_policy();
+    #[cfg(feature = "unicode")]
+    let unicode_rejects = |c| config.unicode_policy().is_some_and(|p| p.rejects_char(c));
+    #[cfg(not(feature = "unicode"))]
+    let unicode_rejects = |_| false;
+
+    let mut rv = String::with_capacity(trimmed.len());
+    for (idx, character) in trimmed.char_indices() {
+        if policy.allows_char(character) && !unicode_rejects(character) {
+            rv.push(character);
+            continue;
+        }
+        let offset = start + idx;
+        let replacement = match character {
+            '/' | '\\' => Some(SEPARATOR_REPLACEMENT),
+            c if c.is_whitespace() && policy.allows_char(' ') => Some(' '),
+            _ => None,
+        };
+        match replacement {
+            Some(replacement) => {
+                rv.push(replacement);
+                changes.push(SanitizeChange::ReplacedCharacter {
+                    character,
+                    replacement,
+                    offset,
+                });
+            }
+            None => changes.push(SanitizeChange::RemovedCharacter { character, offset }),
+        }
+    }
+
+    // removing characters can expose more whitespace at the edges
+    if trimmed.len() != value.len() || rv.trim().len() != rv.len() {
+        changes.insert(0, SanitizeChange::Trimmed);
+    }
+    let mut rv = rv.trim().to_string();
+
+    #[cfg(feature = "unicode")]
+    {
+        if config.unicode_policy().is_some_and(|p| p.requires_nfc()) {
+            if let std::borrow::Cow::Owned(normalized) = crate::unicode::normalize_nfc(&rv) {
+                rv = normalized;
+                changes.push(SanitizeChange::Normalized);
+            }
+        }
+    }
+
+    let length = config.length_of(&rv);
+    if length > config.max_length() {
+        rv = truncate_value(&rv, config);
+        changes.push(SanitizeChange::Truncated {
+            length,
+            limit: config.max_length(),
+        });
+    }
+
+    // truncating can produce a restricted name and the prefix can make the
+    // value too long again, so check the result until it is valid
+    while config.is_restricted(&rv) {
+        changes.push(SanitizeChange::RenamedRestricted { name: rv.clone() });
+        let renamed = format!("{}{}", RESTRICTED_PREFIX, rv);
+        let renamed = if config.length_of(&renamed) > config.max_length() {
+            truncate_value(&renamed, config)
+        } else {
+            renamed
+        };
+        if renamed == rv {
+            break;
+        }
+        rv = renamed;
+    }
+
+    Sanitized { value: rv, changes }
+}
+
+/// Repairs a release so that it passes [`validate_release`](crate::validate_release).
+///
+/// Whitespace is trimmed, `/` and `\` are replaced with `-`, whitespace
+/// control characters with spaces and other control characters are
+/// removed.  With a unicode policy characters it rejects are removed and
+/// the value is normalized if required.  Releases that are too long are
+/// truncated at a character boundary, shortening the package before the
+/// version and keeping a build hash at the end of the version.  Restricted
+/// names are then prefixed with `_`.
+pub fn sanitize_release(release: &str) -> Sanitized {
+    sanitize_release_with(release, &DEFAULT_RELEASE_VALIDATION)
+}
+
+/// Repairs a release so that it passes the given validation rules.
+pub fn sanitize_release_with(release: &str, config: &ValidationConfig) -> Sanitized {
+    sanitize(release, config, truncate_release)
+}
+
+/// Repairs an environment so that it passes
+/// [`validate_environment`](crate::validate_environment).
+///
+/// This applies the same repairs as [`sanitize_release`] but truncates
+/// environments at the end.
+pub fn sanitize_environment(environment: &str) -> Sanitized {
+    sanitize_environment_with(environment, &DEFAULT_ENVIRONMENT_VALIDATION)
+}
+
+/// Repairs an environment so that it passes the given validation rules.
+pub fn sanitize_environment_with(environment: &str, config: &ValidationConfig) -> Sanitized {
+    sanitize(environment, config, |value, config| {
+        truncate(value, config.max_length(), config.length_unit()).to_string()
+    })
+}
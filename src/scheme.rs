@@ -0,0 +1,385 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::config::{InvalidParserConfig, PackageMatcher, ParserConfig};
+use crate::npm::{cmp_pre, Identifier};
+use crate::parser::{is_build_hash, InvalidRelease, Release, Version};
+use crate::pep440::Pep440Scheme;
+use crate::validation::{ValidationConfig, DEFAULT_RELEASE_VALIDATION};
+
+lazy_static! {
+    pub(crate) static ref SEMVER_REGEX: Regex = Regex::new(
+        r"(?x)
+        ^
+            (0|[1-9][0-9]*)\.(0|[1-9][0-9]*)\.(0|[1-9][0-9]*)
+            (?:-(
+                (?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*)
+                (?:\.(?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*))*
+            ))?
+            (?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?
+        $
+        "
+    )
+    .unwrap();
+    static ref DEFAULT_SCHEME: Arc<dyn VersionScheme> = Arc::new(DefaultScheme);
+}
+
+/// A format of versions.
+///
+/// Version schemes teach a [`Parser`] about version formats beyond what
+/// [`Version::parse`] understands, such as internal train names
+/// (`train-42.3`) or vendor firmware strings.  A scheme parses the version
+/// part of a release into a generic [`Version`] view (see
+/// [`Version::from_parts`]) and defines how such versions compare and are
+/// described.
+///
+/// ```
+/// use sentry_release_parser::{Parser, Release, Version, VersionScheme};
+///
+/// struct TrainScheme;
+///
+/// impl VersionScheme for TrainScheme {
+///     fn name(&self) -> &'static str {
+///         "train"
+///     }
+///
+///     fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+///         let rest = version.strip_prefix("train-")?;
+///         let (train, build) = rest.split_once('.')?;
+///         Version::from_parts(version, (train, Some(build), None, None), None, None).ok()
+///     }
+/// }
+///
+/// let mut parser = Parser::new();
+/// parser.register(TrainScheme);
+/// let release = Release::parse_with("app@train-42.3", &parser).unwrap();
+/// assert_eq!(release.scheme(), Some("train"));
+/// assert_eq!(release.version().unwrap().triple(), (42, 3, 0));
+/// ```
+pub trait VersionScheme: Send + Sync {
+    /// Returns the name of the scheme.
+    ///
+    /// This is recorded on releases parsed by the scheme.
+    fn name(&self) -> &'static str;
+
+    /// Parses the version part of a release.
+    ///
+    /// Returns `None` if the scheme does not apply to the version, in which
+    /// case the next scheme is tried.
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>>;
+
+    /// Compares two versions parsed by this scheme.
+    fn compare(&self, a: &Version<'_>, b: &Version<'_>) -> Ordering {
+        a.cmp(b)
+    }
+
+    /// Describes a version parsed by this scheme for humans.
+    ///
+    /// By default this is the version with an abbreviated build code in
+    /// parentheses, the same as [`Release::describe`].
+    fn describe(&self, version: &Version<'_>) -> String {
+        match version.build_code() {
+            Some(code) if is_build_hash(code) => {
+                format!("{} ({})", version.raw_short(), &code[..12])
+            }
+            Some(code) => format!("{} ({})", version.raw_short(), code),
+            None => version.raw_short().to_string(),
+        }
+    }
+}
+
+/// The version scheme implemented by [`Version::parse`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultScheme;
+
+impl VersionScheme for DefaultScheme {
+    fn name(&self) -> &'static str {
+        "default"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        Version::parse(version).ok()
+    }
+}
+
+/// The version scheme a release was parsed with.
+///
+/// Scheme references compare by name so that releases stay comparable.
+#[derive(Clone)]
+pub(crate) struct SchemeRef(Arc<dyn VersionScheme>);
+
+impl SchemeRef {
+    /// Returns the scheme used by [`Release::parse`].
+    pub(crate) fn default_scheme() -> SchemeRef {
+        SchemeRef(DEFAULT_SCHEME.clone())
+    }
+}
+
+impl Deref for SchemeRef {
+    type Target = dyn VersionScheme;
+
+    fn deref(&self) -> &(dyn VersionScheme + 'static) {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for SchemeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.name(), f)
+    }
+}
+
+impl PartialEq for SchemeRef {
+    fn eq(&self, other: &SchemeRef) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for SchemeRef {}
+
+impl Ord for SchemeRef {
+    fn cmp(&self, other: &SchemeRef) -> Ordering {
+        self.name().cmp(other.name())
+    }
+}
+
+impl PartialOrd for SchemeRef {
+    fn partial_cmp(&self, other: &SchemeRef) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Version schemes following semantic versioning 2.0.
+///
+/// This only accepts `MAJOR.MINOR.PATCH` versions with optional pre-release
+/// and build metadata as defined by semver and compares them with semver
+/// precedence, so build metadata does not take part in the comparison and
+/// numeric pre-release identifiers compare numerically.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SemverScheme;
+
+impl VersionScheme for SemverScheme {
+    fn name(&self) -> &'static str {
+        "semver"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        let caps = SEMVER_REGEX.captures(version)?;
+        let part = |idx| caps.get(idx).map(|x| x.as_str());
+        Version::from_parts(
+            version,
+            (part(1)?, part(2), part(3), None),
+            part(4),
+            part(5),
+        )
+        .ok()
+    }
+
+    fn compare(&self, a: &Version<'_>, b: &Version<'_>) -> Ordering {
+        a.triple().cmp(&b.triple()).then_with(|| {
+            cmp_pre(
+                &Identifier::split(a.pre().unwrap_or("")),
+                &Identifier::split(b.pre().unwrap_or("")),
+            )
+        })
+    }
+}
+
+/// Looks up one of the schemes that come with this crate.
+fn builtin_scheme(name: &str) -> Option<Arc<dyn VersionScheme>> {
+    Some(match name {
+        "default" => Arc::new(DefaultScheme),
+        "semver" => Arc::new(SemverScheme),
+        "pep440" => Arc::new(Pep440Scheme),
+        _ => return None,
+    })
+}
+
+/// A release parser with a list of version schemes.
+///
+/// The scheme used for the version part of a release depends on the
+/// package:
+///
+/// - if a rule of the [`ParserConfig`] matches the package, only the scheme
+///   of that rule is used.
+/// - otherwise the registered schemes are tried in the order they were
+///   registered, followed by the fallback scheme.  The fallback is the
+///   [`DefaultScheme`] unless configured otherwise.
+///
+/// Which scheme parsed a version is recorded in [`Release::scheme`].  The
+/// rules that decide whether a release has a version part at all are the
+/// same as for [`Release::parse`]: releases need a package and build hashes
+/// are never parsed as version.  Releases are validated against the
+/// default rules unless a [`ValidationConfig`] is set.
+#[derive(Clone)]
+pub struct Parser {
+    schemes: Vec<Arc<dyn VersionScheme>>,
+    rules: Vec<(PackageMatcher, Arc<dyn VersionScheme>)>,
+    fallback: Arc<dyn VersionScheme>,
+    validation: ValidationConfig,
+}
+
+impl Default for Parser {
+    fn default() -> Parser {
+        Parser {
+            schemes: vec![],
+            rules: vec![],
+            fallback: Arc::new(DefaultScheme),
+            validation: DEFAULT_RELEASE_VALIDATION.clone(),
+        }
+    }
+}
+
+impl Parser {
+    /// Creates a parser that only knows the default scheme.
+    pub fn new() -> Parser {
+        Parser::default()
+    }
+
+    /// Creates a parser from a configuration.
+    ///
+    /// The configuration can refer to the built-in schemes `default`,
+    /// `semver` and `pep440`.  To use custom schemes in a configuration,
+    /// register them first and then call [`configure`](Self::configure).
+    pub fn from_config(config: &ParserConfig) -> Result<Parser, InvalidParserConfig> {
+        let mut parser = Parser::new();
+        parser.configure(config)?;
+        Ok(parser)
+    }
+
+    /// Registers a version scheme.
+    ///
+    /// Schemes are tried in the order they are registered.
+    pub fn register<S: VersionScheme + 'static>(&mut self, scheme: S) -> &mut Parser {
+        self.schemes.push(Arc::new(scheme));
+        self
+    }
+
+    /// Sets the rules releases are validated against.
+    pub fn set_validation(&mut self, validation: ValidationConfig) -> &mut Parser {
+        self.validation = validation;
+        self
+    }
+
+    /// Returns the rules releases are validated against.
+    pub fn validation(&self) -> &ValidationConfig {
+        &self.validation
+    }
+
+    /// Applies a configuration.
+    ///
+    /// The rules of the configuration are added after existing rules.
+    /// Scheme names are resolved against the registered schemes first and
+    /// then against the built-in schemes.
+    pub fn configure(&mut self, config: &ParserConfig) -> Result<&mut Parser, InvalidParserConfig> {
+        let resolve = |name: &str| {
+            self.schemes
+                .iter()
+                .find(|scheme| scheme.name() == name)
+                .cloned()
+                .or_else(|| builtin_scheme(name))
+                .ok_or_else(|| InvalidParserConfig::new(name))
+        };
+        let rules = config
+            .rules()
+            .iter()
+            .map(|rule| Ok((rule.matcher().clone(), resolve(rule.scheme())?)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fallback = config.fallback().map(resolve).transpose()?;
+
+        self.rules.extend(rules);
+        if let Some(fallback) = fallback {
+            self.fallback = fallback;
+        }
+        Ok(self)
+    }
+
+    /// Iterates over the schemes tried for packages without a rule.
+    pub fn schemes(&self) -> impl Iterator<Item = &dyn VersionScheme> + '_ {
+        self.schemes
+            .iter()
+            .chain(std::iter::once(&self.fallback))
+            .map(|scheme| &**scheme)
+    }
+
+    /// Looks up a scheme known to the parser by name.
+    pub fn scheme(&self, name: &str) -> Option<&dyn VersionScheme> {
+        self.schemes()
+            .chain(self.rules.iter().map(|rule| &*rule.1))
+            .find(|scheme| scheme.name() == name)
+    }
+
+    /// Parses a release.
+    ///
+    /// This is equivalent to [`Release::parse_with`].
+    pub fn parse<'a>(&self, release: &'a str) -> Result<Release<'a>, InvalidRelease> {
+        Release::parse_with(release, self)
+    }
+
+    pub(crate) fn parse_version<'a>(
+        &self,
+        package: &str,
+        version: &'a str,
+    ) -> Option<(Version<'a>, SchemeRef)> {
+        if let Some((_, scheme)) = self.rules.iter().find(|rule| rule.0.matches(package)) {
+            return scheme
+                .parse(version)
+                .map(|v| (v, SchemeRef(scheme.clone())));
+        }
+        self.schemes
+            .iter()
+            .chain(std::iter::once(&self.fallback))
+            .find_map(|scheme| {
+                scheme
+                    .parse(version)
+                    .map(|v| (v, SchemeRef(scheme.clone())))
+            })
+    }
+
+    /// Compares the versions of two releases.
+    ///
+    /// Returns `None` unless both releases belong to the same package and
+    /// have versions parsed by the same scheme.  This is the same as
+    /// [`Release::cmp_version`].
+    pub fn compare(&self, a: &Release<'_>, b: &Release<'_>) -> Option<Ordering> {
+        a.cmp_version(b)
+    }
+
+    /// Describes the version of a release with the scheme that parsed it.
+    ///
+    /// Releases without a version are described as by [`Release::describe`].
+    pub fn describe(&self, release: &Release<'_>) -> String {
+        match (
+            release.version(),
+            release.scheme().and_then(|s| self.scheme(s)),
+        ) {
+            (Some(version), Some(scheme)) => scheme.describe(version),
+            _ => release.describe().to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for Parser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Parser")
+            .field(
+                "schemes",
+                &self.schemes().map(|s| s.name()).collect::<Vec<_>>(),
+            )
+            .field(
+                "rules",
+                &self
+                    .rules
+                    .iter()
+                    .map(|(matcher, scheme)| (matcher, scheme.name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("validation", &self.validation)
+            .finish()
+    }
+}
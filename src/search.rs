@@ -0,0 +1,524 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::glob::{glob_match, is_glob};
+use crate::parser::{Release, Version};
+use crate::pattern::{parse_component_wildcard, quad_array, split_package};
+
+lazy_static! {
+    pub(crate) static ref KEY_REGEX: Regex =
+        Regex::new(r"(?s)^(!?)([a-zA-Z0-9_.]+):(.*)$").unwrap();
+}
+
+/// An error indicating an invalid search query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidSearchQuery;
+
+impl std::error::Error for InvalidSearchQuery {}
+
+impl fmt::Display for InvalidSearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid search query")
+    }
+}
+
+/// The release keys understood in search queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchKey {
+    /// `release`: the full release name, supports wildcards.
+    Release,
+    /// `release.version`: the version, optionally prefixed by a package.
+    Version,
+    /// `release.package`: the package, supports wildcards.
+    Package,
+    /// `release.build`: the build number or build code.
+    Build,
+    /// `release.stage`: the adoption stage.
+    Stage,
+}
+
+impl SearchKey {
+    fn parse(key: &str) -> Option<SearchKey> {
+        Some(match key {
+            "release" => SearchKey::Release,
+            "release.version" => SearchKey::Version,
+            "release.package" => SearchKey::Package,
+            "release.build" => SearchKey::Build,
+            "release.stage" => SearchKey::Stage,
+            _ => return None,
+        })
+    }
+
+    /// Returns the key as it appears in queries.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKey::Release => "release",
+            SearchKey::Version => "release.version",
+            SearchKey::Package => "release.package",
+            SearchKey::Build => "release.build",
+            SearchKey::Stage => "release.stage",
+        }
+    }
+}
+
+impl fmt::Display for SearchKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The operator of a search filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchOperator {
+    /// Matches equal values (no operator).
+    Equal,
+    /// Matches lower values (`<`).
+    Less,
+    /// Matches lower or equal values (`<=`).
+    LessOrEqual,
+    /// Matches higher values (`>`).
+    Greater,
+    /// Matches higher or equal values (`>=`).
+    GreaterOrEqual,
+}
+
+impl SearchOperator {
+    /// Returns the operator as it appears in queries.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchOperator::Equal => "",
+            SearchOperator::Less => "<",
+            SearchOperator::LessOrEqual => "<=",
+            SearchOperator::Greater => ">",
+            SearchOperator::GreaterOrEqual => ">=",
+        }
+    }
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            SearchOperator::Equal => ordering == Ordering::Equal,
+            SearchOperator::Less => ordering == Ordering::Less,
+            SearchOperator::LessOrEqual => ordering != Ordering::Greater,
+            SearchOperator::Greater => ordering == Ordering::Greater,
+            SearchOperator::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// The adoption stage of a release.
+///
+/// Stages are not part of the release name and have to be supplied by the
+/// caller when evaluating `release.stage` filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReleaseStage {
+    /// The release is adopted by a significant share of sessions.
+    Adopted,
+    /// The release is in use but not yet widely adopted.
+    LowAdoption,
+    /// The release was adopted before and has been replaced by a newer one.
+    Replaced,
+}
+
+impl ReleaseStage {
+    fn parse(stage: &str) -> Option<ReleaseStage> {
+        Some(match stage {
+            "adopted" => ReleaseStage::Adopted,
+            "low_adoption" => ReleaseStage::LowAdoption,
+            "replaced" => ReleaseStage::Replaced,
+            _ => return None,
+        })
+    }
+
+    /// Returns the name of the stage as it appears in queries.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseStage::Adopted => "adopted",
+            ReleaseStage::LowAdoption => "low_adoption",
+            ReleaseStage::Replaced => "replaced",
+        }
+    }
+}
+
+/// A version value of a `release.version` filter.
+///
+/// The version may end in `.*` (or be just `*`) in which case only the
+/// components given are compared.
+struct VersionValue<'a> {
+    package: Option<&'a str>,
+    version: Option<Version<'a>>,
+    components: Option<usize>,
+}
+
+impl<'a> VersionValue<'a> {
+    fn parse(value: &'a str, allow_wildcard: bool) -> Option<VersionValue<'a>> {
+        let (package, version) = split_package(value);
+        let (version, components) = match parse_component_wildcard(version) {
+            _ if version == "*" && allow_wildcard => {
+                return Some(VersionValue {
+                    package,
+                    version: None,
+                    components: Some(0),
+                })
+            }
+            Some((prefix, components)) if allow_wildcard => (prefix, Some(components)),
+            Some(_) => return None,
+            None => (version, None),
+        };
+        Some(VersionValue {
+            package,
+            version: Some(Version::parse(version).ok()?),
+            components,
+        })
+    }
+
+    fn cmp_to(&self, release: &Release<'_>) -> Option<Ordering> {
+        let version = release.version()?;
+        if self.package.is_some() && self.package != release.package() {
+            return None;
+        }
+        let expected = match self.version {
+            Some(ref expected) => expected,
+            None => return Some(Ordering::Equal),
+        };
+        Some(match self.components {
+            Some(components) => {
+                quad_array(version)[..components].cmp(&quad_array(expected)[..components])
+            }
+            None => version.cmp(expected),
+        })
+    }
+}
+
+/// A single filter term of a search query.
+///
+/// A filter is written as `key:value`, optionally prefixed with `!` to negate
+/// it.  The value can be prefixed with a comparison operator or be a list of
+/// alternatives in brackets (`release:[a,b]`).  Values can be quoted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchFilter {
+    key: SearchKey,
+    negated: bool,
+    operator: SearchOperator,
+    values: Vec<String>,
+}
+
+impl SearchFilter {
+    /// Returns the key the filter applies to.
+    pub fn key(&self) -> SearchKey {
+        self.key
+    }
+
+    /// Returns `true` if the filter is negated.
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Returns the operator of the filter.
+    pub fn operator(&self) -> SearchOperator {
+        self.operator
+    }
+
+    /// Returns the unquoted values of the filter.
+    ///
+    /// Only filters with a list have more than one value.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    fn validate(&self) -> bool {
+        let is_equal = self.operator == SearchOperator::Equal;
+        self.values.iter().all(|value| match self.key {
+            SearchKey::Release | SearchKey::Package => is_equal,
+            SearchKey::Version => VersionValue::parse(value, is_equal).is_some(),
+            SearchKey::Build => is_equal || value.parse::<u64>().is_ok(),
+            SearchKey::Stage => is_equal && ReleaseStage::parse(value).is_some(),
+        })
+    }
+
+    /// Checks if the filter matches a release.
+    ///
+    /// `release.stage` filters only match if the stage is known.
+    pub fn matches_with_stage(&self, release: &Release<'_>, stage: Option<ReleaseStage>) -> bool {
+        let matched = self.values.iter().any(|value| match self.key {
+            SearchKey::Release => glob_match(value, release.raw()),
+            SearchKey::Package => release
+                .package()
+                .is_some_and(|package| glob_match(value, package)),
+            SearchKey::Version => VersionValue::parse(value, true)
+                .and_then(|value| value.cmp_to(release))
+                .is_some_and(|ordering| self.operator.accepts(ordering)),
+            SearchKey::Build => self.matches_build(value, release),
+            SearchKey::Stage => stage.is_some_and(|stage| stage.as_str() == value),
+        });
+        matched != self.negated
+    }
+
+    fn matches_build(&self, value: &str, release: &Release<'_>) -> bool {
+        let version = match release.version() {
+            Some(version) => version,
+            None => return false,
+        };
+        if let (Ok(expected), Some(actual)) = (value.parse::<u64>(), version.build_number()) {
+            return self.operator.accepts(actual.cmp(&expected));
+        }
+        self.operator == SearchOperator::Equal
+            && version.build_code().is_some_and(|code| {
+                if is_glob(value) {
+                    glob_match(value, code)
+                } else {
+                    code == value
+                }
+            })
+    }
+}
+
+impl fmt::Display for SearchFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_value(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+            if value.is_empty() || value.contains(&[' ', '"', ',', '[', ']'][..]) {
+                write!(
+                    f,
+                    "\"{}\"",
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            } else {
+                write!(f, "{}", value)
+            }
+        }
+
+        if self.negated {
+            write!(f, "!")?;
+        }
+        write!(f, "{}:{}", self.key, self.operator.as_str())?;
+        if self.values.len() == 1 {
+            return write_value(f, &self.values[0]);
+        }
+        write!(f, "[")?;
+        for (idx, value) in self.values.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write_value(f, value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Splits a query into whitespace separated terms.
+///
+/// Whitespace within quotes and brackets does not separate terms.
+fn tokenize(query: &str) -> Result<Vec<&str>, InvalidSearchQuery> {
+    let mut tokens = vec![];
+    let mut start = None;
+    let mut in_quote = false;
+    let mut escaped = false;
+    let mut depth = 0;
+
+    for (idx, c) in query.char_indices() {
+        if in_quote {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_quote = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    tokens.push(&query[start..idx]);
+                }
+                continue;
+            }
+            '"' => in_quote = true,
+            '[' => depth += 1,
+            ']' if depth == 0 => return Err(InvalidSearchQuery),
+            ']' => depth -= 1,
+            _ => {}
+        }
+        start.get_or_insert(idx);
+    }
+
+    if in_quote || depth > 0 {
+        return Err(InvalidSearchQuery);
+    }
+    if let Some(start) = start {
+        tokens.push(&query[start..]);
+    }
+    Ok(tokens)
+}
+
+/// Removes the quotes around a value and resolves escapes.
+fn unquote(value: &str) -> Result<String, InvalidSearchQuery> {
+    let value = value.trim();
+    let inner = match value.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"').ok_or(InvalidSearchQuery)?,
+        None if value.is_empty() || value.contains('"') => return Err(InvalidSearchQuery),
+        None => return Ok(value.to_string()),
+    };
+    let mut rv = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => rv.push(chars.next().ok_or(InvalidSearchQuery)?),
+            '"' => return Err(InvalidSearchQuery),
+            c => rv.push(c),
+        }
+    }
+    Ok(rv)
+}
+
+/// Splits the items of a list on commas outside of quotes.
+fn split_list(list: &str) -> Result<Vec<String>, InvalidSearchQuery> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut in_quote = false;
+    let mut escaped = false;
+    for (idx, c) in list.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quote => escaped = true,
+            '"' => in_quote = !in_quote,
+            ',' if !in_quote => {
+                items.push(unquote(&list[start..idx])?);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(unquote(&list[start..])?);
+    Ok(items)
+}
+
+fn parse_filter(
+    key: SearchKey,
+    negated: bool,
+    value: &str,
+) -> Result<SearchFilter, InvalidSearchQuery> {
+    let (operator, value) = [
+        (">=", SearchOperator::GreaterOrEqual),
+        ("<=", SearchOperator::LessOrEqual),
+        (">", SearchOperator::Greater),
+        ("<", SearchOperator::Less),
+        ("=", SearchOperator::Equal),
+    ]
+    .iter()
+    .find_map(|&(prefix, op)| value.strip_prefix(prefix).map(|rest| (op, rest)))
+    .unwrap_or((SearchOperator::Equal, value));
+
+    let values = match value.strip_prefix('[') {
+        Some(list) if operator == SearchOperator::Equal => {
+            split_list(list.strip_suffix(']').ok_or(InvalidSearchQuery)?)?
+        }
+        Some(_) => return Err(InvalidSearchQuery),
+        None => vec![unquote(value)?],
+    };
+
+    let filter = SearchFilter {
+        key,
+        negated,
+        operator,
+        values,
+    };
+    if filter.validate() {
+        Ok(filter)
+    } else {
+        Err(InvalidSearchQuery)
+    }
+}
+
+/// A parsed Sentry search query restricted to release filters.
+///
+/// This understands the `release`, `release.version`, `release.package`,
+/// `release.build` and `release.stage` filters.  All filters have to match.
+/// Terms for other keys and free text are ignored so that full search
+/// queries can be passed in.
+///
+/// - `release` and `release.package` match the release name and package,
+///   `*` acts as wildcard.
+/// - `release.version` compares versions with an optional comparison
+///   operator.  The value can carry a package (`release.version:>=foo@1.0`)
+///   and versions can end in `.*` to only compare the given components.
+/// - `release.build` compares build numbers numerically and build codes by
+///   string or wildcard.
+/// - `release.stage` matches one of `adopted`, `low_adoption` or `replaced`.
+///
+/// ```
+/// use sentry_release_parser::{Release, SearchQuery};
+///
+/// let query = SearchQuery::parse("is:unresolved release.version:>=1.2.0 release.package:com.acme.*").unwrap();
+/// assert!(query.matches(&Release::parse("com.acme.app@1.4.0").unwrap()));
+/// assert!(!query.matches(&Release::parse("com.acme.app@1.0.0").unwrap()));
+/// assert!(!query.matches(&Release::parse("com.other@1.4.0").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    filters: Vec<SearchFilter>,
+}
+
+impl SearchQuery {
+    /// Parses the release filters out of a search query.
+    pub fn parse(query: &str) -> Result<SearchQuery, InvalidSearchQuery> {
+        let mut filters = vec![];
+        for token in tokenize(query)? {
+            if token == "OR" || token.starts_with('(') || token.ends_with(')') {
+                // boolean expressions would silently change the meaning
+                return Err(InvalidSearchQuery);
+            }
+            let caps = match KEY_REGEX.captures(token) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            if let Some(key) = SearchKey::parse(&caps[2]) {
+                let negated = !caps[1].is_empty();
+                filters.push(parse_filter(key, negated, caps.get(3).unwrap().as_str())?);
+            }
+        }
+        Ok(SearchQuery { filters })
+    }
+
+    /// Returns the release filters of the query.
+    pub fn filters(&self) -> &[SearchFilter] {
+        &self.filters
+    }
+
+    /// Checks if the release matches all filters.
+    ///
+    /// As the stage of the release is not known, `release.stage` filters
+    /// never match (and negated ones always do).
+    pub fn matches(&self, release: &Release<'_>) -> bool {
+        self.matches_with_stage(release, None)
+    }
+
+    /// Checks if the release in the given stage matches all filters.
+    pub fn matches_with_stage(&self, release: &Release<'_>, stage: Option<ReleaseStage>) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.matches_with_stage(release, stage))
+    }
+}
+
+impl FromStr for SearchQuery {
+    type Err = InvalidSearchQuery;
+
+    fn from_str(s: &str) -> Result<SearchQuery, InvalidSearchQuery> {
+        SearchQuery::parse(s)
+    }
+}
+
+impl fmt::Display for SearchQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, filter) in self.filters.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", filter)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::parser::Version;
+
+const PRE_SOME: u8 = 0x01;
+const PRE_NONE: u8 = 0x02;
+const BUILD_NONE: u8 = 0x00;
+const BUILD_NUMBER: u8 = 0x01;
+const BUILD_CODE: u8 = 0x02;
+
+/// An error indicating an invalid sort key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidSortKey;
+
+impl std::error::Error for InvalidSortKey {}
+
+impl fmt::Display for InvalidSortKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid sort key")
+    }
+}
+
+/// An owned binary key that sorts like a [`Version`].
+///
+/// The bytes of the key compare lexicographically in the same order as
+/// `Version::cmp` which makes it suitable to be stored in a database column
+/// (for instance `bytea` in Postgres or `String` in ClickHouse) and to be
+/// used with `ORDER BY`.
+///
+/// The key is laid out as follows:
+///
+/// - the four version components as big endian 64bit integers
+/// - the pre-release: `0x01` followed by the pre-release and a `0x00`
+///   terminator, or `0x02` if there is no pre-release so that pre-releases
+///   sort before the release
+/// - the build code: `0x00` if there is none, `0x01` followed by the build
+///   number as big endian 64bit integer and the raw build code if the build
+///   code is numeric, or `0x02` followed by the raw build code otherwise
+///
+/// `Version::cmp` compares two numeric build codes by value and falls back
+/// to comparing the build codes as strings otherwise.  That comparison is not
+/// transitive when numeric and non numeric build codes are mixed (`+2 < +10`,
+/// `+10 < +1a` but `+1a < +2`) which no binary key can reproduce.  The key
+/// instead orders all numeric build codes before all other build codes.
+/// Additionally the key retains the formatting of numeric build codes, so
+/// `+010` sorts right before `+10` even though the versions compare equal.
+/// In all other cases the order of the keys is the same as the order of the
+/// versions.
+///
+/// Pre-releases of versions never contain the `0x00` terminator, so every
+/// key created from a version can be decoded again.  Keys from other sources
+/// are validated by [`SortKey::from_bytes`].
+///
+/// ```
+/// use sentry_release_parser::{SortKey, Version};
+///
+/// let a = Version::parse("1.0.0-rc1").unwrap();
+/// let b = Version::parse("1.0.0").unwrap();
+/// assert!(a.sort_key() < b.sort_key());
+///
+/// let key = SortKey::new(&a);
+/// assert_eq!(key.to_string(), "1.0.0.0-rc1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey(Vec<u8>);
+
+struct Decoded<'a> {
+    pre: Option<&'a str>,
+    build_code: Option<&'a str>,
+}
+
+fn read_u64(bytes: &[u8]) -> Result<(u64, &[u8]), InvalidSortKey> {
+    if bytes.len() < 8 {
+        return Err(InvalidSortKey);
+    }
+    let (num, rest) = bytes.split_at(8);
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(num);
+    Ok((u64::from_be_bytes(buf), rest))
+}
+
+fn decode(bytes: &[u8]) -> Result<Decoded<'_>, InvalidSortKey> {
+    let rest = bytes.get(32..).ok_or(InvalidSortKey)?;
+
+    let (pre, rest) = match rest.split_first() {
+        Some((&PRE_NONE, rest)) => (None, rest),
+        Some((&PRE_SOME, rest)) => {
+            let end = rest.iter().position(|&b| b == 0).ok_or(InvalidSortKey)?;
+            let pre = std::str::from_utf8(&rest[..end]).map_err(|_| InvalidSortKey)?;
+            if pre.is_empty() {
+                return Err(InvalidSortKey);
+            }
+            (Some(pre), &rest[end + 1..])
+        }
+        _ => return Err(InvalidSortKey),
+    };
+
+    let build_code = match rest.split_first() {
+        Some((&BUILD_NONE, &[])) => None,
+        Some((&BUILD_NUMBER, rest)) => {
+            let (num, rest) = read_u64(rest)?;
+            let code = std::str::from_utf8(rest).map_err(|_| InvalidSortKey)?;
+            if code.parse::<u64>().ok() != Some(num) {
+                return Err(InvalidSortKey);
+            }
+            Some(code)
+        }
+        Some((&BUILD_CODE, rest)) => {
+            let code = std::str::from_utf8(rest).map_err(|_| InvalidSortKey)?;
+            if code.is_empty() || code.parse::<u64>().is_ok() {
+                return Err(InvalidSortKey);
+            }
+            Some(code)
+        }
+        _ => return Err(InvalidSortKey),
+    };
+
+    Ok(Decoded { pre, build_code })
+}
+
+impl SortKey {
+    /// Creates the sort key for a version.
+    pub fn new(version: &Version<'_>) -> SortKey {
+        let pre = version.pre();
+        let build_code = version.build_code();
+        let mut bytes = Vec::with_capacity(
+            34 + pre.map_or(0, |x| x.len() + 1) + build_code.map_or(0, |x| x.len() + 8),
+        );
+
+        let (major, minor, patch, revision) = version.quad();
+        for component in [major, minor, patch, revision] {
+            bytes.extend_from_slice(&component.to_be_bytes());
+        }
+
+        match pre {
+            Some(pre) => {
+                bytes.push(PRE_SOME);
+                bytes.extend_from_slice(pre.as_bytes());
+                bytes.push(0);
+            }
+            None => bytes.push(PRE_NONE),
+        }
+
+        match (build_code, version.build_number()) {
+            (None, _) => bytes.push(BUILD_NONE),
+            (Some(code), Some(num)) => {
+                bytes.push(BUILD_NUMBER);
+                bytes.extend_from_slice(&num.to_be_bytes());
+                bytes.extend_from_slice(code.as_bytes());
+            }
+            (Some(code), None) => {
+                bytes.push(BUILD_CODE);
+                bytes.extend_from_slice(code.as_bytes());
+            }
+        }
+
+        SortKey(bytes)
+    }
+
+    /// Creates a sort key from its binary representation.
+    ///
+    /// This fails if the bytes were not produced by [`SortKey::new`].
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<SortKey, InvalidSortKey> {
+        decode(&bytes)?;
+        Ok(SortKey(bytes))
+    }
+
+    /// Returns the binary representation of the key.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Converts the key into its binary representation.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Returns the version quadruple encoded in the key.
+    pub fn quad(&self) -> (u64, u64, u64, u64) {
+        let component = |idx: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&self.0[idx * 8..idx * 8 + 8]);
+            u64::from_be_bytes(buf)
+        };
+        (component(0), component(1), component(2), component(3))
+    }
+
+    /// Returns the pre-release encoded in the key.
+    pub fn pre(&self) -> Option<&str> {
+        decode(&self.0).ok()?.pre
+    }
+
+    /// Returns the build code encoded in the key.
+    pub fn build_code(&self) -> Option<&str> {
+        decode(&self.0).ok()?.build_code
+    }
+}
+
+impl<'a> From<&Version<'a>> for SortKey {
+    fn from(version: &Version<'a>) -> SortKey {
+        SortKey::new(version)
+    }
+}
+
+/// Formats the key back into a version string.
+///
+/// The output always has four components and parses into a version that
+/// compares equal to the version the key was created from.
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (major, minor, patch, revision) = self.quad();
+        write!(f, "{}.{}.{}.{}", major, minor, patch, revision)?;
+        if let Some(pre) = self.pre() {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build_code) = self.build_code() {
+            write!(f, "+{}", build_code)?;
+        }
+        Ok(())
+    }
+}
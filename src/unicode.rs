@@ -0,0 +1,254 @@
+use std::fmt;
+
+#[cfg(feature = "unicode")]
+use std::borrow::Cow;
+
+#[cfg(feature = "unicode")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+#[cfg(feature = "unicode")]
+use unicode_script::{Script, UnicodeScript};
+
+/// A unicode problem that makes a value look like a different one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnicodeIssue {
+    /// The value contains a bidirectional control character.
+    BidiControl {
+        /// The control character.
+        character: char,
+        /// The byte offset of the character.
+        offset: usize,
+    },
+    /// The value contains an invisible zero-width character.
+    ZeroWidth {
+        /// The zero-width character.
+        character: char,
+        /// The byte offset of the character.
+        offset: usize,
+    },
+    /// The value is not in normalization form C.
+    NotNormalized,
+    /// The package mixes characters of different scripts.
+    MixedScript {
+        /// The first script used in the package that may not be mixed
+        /// with the second one.
+        first: &'static str,
+        /// Another script used in the package.
+        second: &'static str,
+    },
+}
+
+impl fmt::Display for UnicodeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UnicodeIssue::BidiControl { character, offset } => write!(
+                f,
+                "bidirectional control character {:?} at position {}",
+                character, offset
+            ),
+            UnicodeIssue::ZeroWidth { character, offset } => write!(
+                f,
+                "zero-width character {:?} at position {}",
+                character, offset
+            ),
+            UnicodeIssue::NotNormalized => write!(f, "not in normalization form C"),
+            UnicodeIssue::MixedScript { first, second } => {
+                write!(f, "package mixes {} and {} script", first, second)
+            }
+        }
+    }
+}
+
+/// Checks if a character changes the direction of text.
+#[cfg(feature = "unicode")]
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061c}' | '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Checks if a character is invisible and takes up no space.
+#[cfg(feature = "unicode")]
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{180e}' | '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}'
+    )
+}
+
+/// Script combinations that are commonly written together.
+///
+/// These are the combinations the highly restrictive level of UTS #39
+/// allows: Japanese, Chinese and Korean text mixed with latin.
+#[cfg(feature = "unicode")]
+const ALLOWED_SCRIPT_MIXES: &[&[Script]] = &[
+    &[
+        Script::Latin,
+        Script::Han,
+        Script::Hiragana,
+        Script::Katakana,
+    ],
+    &[Script::Latin, Script::Han, Script::Bopomofo],
+    &[Script::Latin, Script::Han, Script::Hangul],
+];
+
+/// Checks if all scripts belong to one of the allowed combinations.
+#[cfg(feature = "unicode")]
+fn is_allowed_script_mix(scripts: &[Script]) -> bool {
+    ALLOWED_SCRIPT_MIXES
+        .iter()
+        .any(|mix| scripts.iter().all(|script| mix.contains(script)))
+}
+
+/// Finds two scripts that no allowed combination contains together.
+#[cfg(feature = "unicode")]
+fn disallowed_script_pair(scripts: &[Script]) -> Option<(Script, Script)> {
+    scripts.iter().enumerate().find_map(|(idx, &first)| {
+        scripts[idx + 1..]
+            .iter()
+            .find(|&&second| !is_allowed_script_mix(&[first, second]))
+            .map(|&second| (first, second))
+    })
+}
+
+/// Normalizes a value to normalization form C.
+///
+/// Values that are already normalized are returned unchanged.
+#[cfg(feature = "unicode")]
+pub fn normalize_nfc(value: &str) -> Cow<'_, str> {
+    if is_nfc(value) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(value.nfc().collect())
+    }
+}
+
+/// Opt-in unicode checks for releases and environments.
+///
+/// The checks reject values that render the same as other values:
+/// bidirectional control and zero-width characters, text that is not in
+/// normalization form C and packages that mix scripts (such as a cyrillic
+/// `а` in an otherwise latin name).  All checks are enabled by default and
+/// can be turned off individually.  Characters of the common and inherited
+/// scripts such as digits and punctuation do not count as a script, and
+/// latin mixed with Japanese, Chinese or Korean scripts is allowed.
+///
+/// The policy is applied through a [`ValidationConfig`](crate::ValidationConfig):
+///
+/// ```
+/// use sentry_release_parser::{Parser, Release, UnicodePolicy, ValidationConfig};
+///
+/// let mut parser = Parser::new();
+/// parser.set_validation(ValidationConfig::release().with_unicode_policy(UnicodePolicy::new()));
+/// assert!(Release::parse_with("myapp@1.0", &parser).is_ok());
+/// assert!(Release::parse_with("my\u{200b}app@1.0", &parser).is_err());
+/// assert!(Release::parse_with("myаpp@1.0", &parser).is_err());
+/// ```
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnicodePolicy {
+    bidi: bool,
+    zero_width: bool,
+    nfc: bool,
+    mixed_script: bool,
+}
+
+#[cfg(feature = "unicode")]
+impl Default for UnicodePolicy {
+    fn default() -> UnicodePolicy {
+        UnicodePolicy {
+            bidi: true,
+            zero_width: true,
+            nfc: true,
+            mixed_script: true,
+        }
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl UnicodePolicy {
+    /// Creates a policy with all checks enabled.
+    pub fn new() -> UnicodePolicy {
+        UnicodePolicy::default()
+    }
+
+    /// Sets whether bidirectional control characters are rejected.
+    pub fn with_bidi_check(mut self, enabled: bool) -> UnicodePolicy {
+        self.bidi = enabled;
+        self
+    }
+
+    /// Sets whether zero-width characters are rejected.
+    pub fn with_zero_width_check(mut self, enabled: bool) -> UnicodePolicy {
+        self.zero_width = enabled;
+        self
+    }
+
+    /// Sets whether values must be in normalization form C.
+    pub fn with_nfc_check(mut self, enabled: bool) -> UnicodePolicy {
+        self.nfc = enabled;
+        self
+    }
+
+    /// Sets whether packages that mix scripts are rejected.
+    pub fn with_mixed_script_check(mut self, enabled: bool) -> UnicodePolicy {
+        self.mixed_script = enabled;
+        self
+    }
+
+    /// Returns `true` if values must be in normalization form C.
+    pub fn requires_nfc(&self) -> bool {
+        self.nfc
+    }
+
+    /// Checks if a single character is rejected.
+    pub fn rejects_char(&self, c: char) -> bool {
+        (self.bidi && is_bidi_control(c)) || (self.zero_width && is_zero_width(c))
+    }
+
+    /// Checks a value against the policy.
+    ///
+    /// The mixed script check applies to the package of releases, that is
+    /// everything before the first `@` that does not start the value.
+    pub fn check(&self, value: &str) -> Result<(), UnicodeIssue> {
+        for (offset, character) in value.char_indices() {
+            if self.bidi && is_bidi_control(character) {
+                return Err(UnicodeIssue::BidiControl { character, offset });
+            } else if self.zero_width && is_zero_width(character) {
+                return Err(UnicodeIssue::ZeroWidth { character, offset });
+            }
+        }
+
+        if self.nfc && !is_nfc(value) {
+            return Err(UnicodeIssue::NotNormalized);
+        }
+
+        if self.mixed_script {
+            let package = value
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '@')
+                .map(|(idx, _)| &value[..idx]);
+            if let Some(package) = package {
+                let mut scripts = vec![];
+                for script in package.chars().map(|c| c.script()) {
+                    if !matches!(script, Script::Common | Script::Inherited | Script::Unknown)
+                        && !scripts.contains(&script)
+                    {
+                        scripts.push(script);
+                    }
+                }
+                if scripts.len() > 1 && !is_allowed_script_mix(&scripts) {
+                    let (first, second) =
+                        disallowed_script_pair(&scripts).unwrap_or((scripts[0], scripts[1]));
+                    return Err(UnicodeIssue::MixedScript {
+                        first: first.full_name(),
+                        second: second.full_name(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
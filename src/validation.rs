@@ -0,0 +1,279 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::parser::{InvalidEnvironment, InvalidRelease};
+use crate::unicode::UnicodeIssue;
+#[cfg(feature = "unicode")]
+use crate::unicode::UnicodePolicy;
+
+lazy_static! {
+    // what can or cannot go through the API which is a limiting factor for
+    // releases and environments.
+    pub(crate) static ref VALID_API_ATTRIBUTE_REGEX: Regex = Regex::new(r"^[^\\/\r\n\t\x7f\x00-\x1f]*\z").unwrap();
+    pub(crate) static ref DEFAULT_RELEASE_VALIDATION: ValidationConfig = ValidationConfig::release();
+    pub(crate) static ref DEFAULT_ENVIRONMENT_VALIDATION: ValidationConfig = ValidationConfig::environment();
+}
+
+/// How the length of a value is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    /// The length is the number of UTF-8 bytes.
+    Bytes,
+    /// The length is the number of unicode scalar values.
+    Chars,
+}
+
+/// The characters a value may contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharacterPolicy {
+    /// Everything the Sentry API accepts: no control characters, `\` or `/`.
+    Api,
+    /// Only printable ASCII characters except `\` and `/`.
+    PrintableAscii,
+}
+
+impl CharacterPolicy {
+    /// Checks if a single character is allowed.
+    pub fn allows_char(self, c: char) -> bool {
+        match self {
+            CharacterPolicy::Api => !c.is_ascii_control() && c != '\\' && c != '/',
+            CharacterPolicy::PrintableAscii => (' '..='~').contains(&c) && c != '\\' && c != '/',
+        }
+    }
+
+    /// Finds the first character that is not allowed and its byte offset.
+    fn find_disallowed(self, value: &str) -> Option<(usize, char)> {
+        if self == CharacterPolicy::Api && VALID_API_ATTRIBUTE_REGEX.is_match(value) {
+            return None;
+        }
+        value.char_indices().find(|&(_, c)| !self.allows_char(c))
+    }
+}
+
+/// A rule that a value violated.
+enum Violation {
+    TooLong {
+        length: usize,
+        limit: usize,
+    },
+    RestrictedName,
+    BadCharacters {
+        character: char,
+        offset: usize,
+    },
+    #[cfg_attr(not(feature = "unicode"), allow(dead_code))]
+    UnsafeUnicode(UnicodeIssue),
+}
+
+/// Configures the rules releases and environments are validated against.
+///
+/// The defaults for releases and environments are what Sentry enforces and
+/// are used by [`validate_release`](crate::validate_release) and
+/// [`validate_environment`](crate::validate_environment).  Deployments with
+/// different limits can start from these and adjust them:
+///
+/// ```
+/// use sentry_release_parser::{validate_release_with, InvalidRelease, ValidationConfig};
+///
+/// let config = ValidationConfig::release()
+///     .with_max_length(20)
+///     .with_restricted_name("unknown");
+/// assert_eq!(validate_release_with("1.0", &config), Ok(()));
+/// assert_eq!(
+///     validate_release_with("Unknown", &config),
+///     Err(InvalidRelease::RestrictedName)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationConfig {
+    max_length: usize,
+    length_unit: LengthUnit,
+    restricted_names: Vec<String>,
+    case_sensitive: bool,
+    character_policy: CharacterPolicy,
+    #[cfg(feature = "unicode")]
+    unicode_policy: Option<UnicodePolicy>,
+}
+
+impl ValidationConfig {
+    /// Creates the default rules for releases.
+    ///
+    /// Releases can be up to 200 bytes long and must not be `.`, `..` or
+    /// `latest`.
+    pub fn release() -> ValidationConfig {
+        ValidationConfig {
+            max_length: 200,
+            length_unit: LengthUnit::Bytes,
+            restricted_names: vec![".".into(), "..".into(), "latest".into()],
+            case_sensitive: false,
+            character_policy: CharacterPolicy::Api,
+            #[cfg(feature = "unicode")]
+            unicode_policy: None,
+        }
+    }
+
+    /// Creates the default rules for environments.
+    ///
+    /// Environments can be up to 64 bytes long and must not be `.`, `..` or
+    /// `none`.
+    pub fn environment() -> ValidationConfig {
+        ValidationConfig {
+            max_length: 64,
+            length_unit: LengthUnit::Bytes,
+            restricted_names: vec![".".into(), "..".into(), "none".into()],
+            case_sensitive: false,
+            character_policy: CharacterPolicy::Api,
+            #[cfg(feature = "unicode")]
+            unicode_policy: None,
+        }
+    }
+
+    /// Sets the maximum length.
+    pub fn with_max_length(mut self, max_length: usize) -> ValidationConfig {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Sets how the length is measured.
+    pub fn with_length_unit(mut self, length_unit: LengthUnit) -> ValidationConfig {
+        self.length_unit = length_unit;
+        self
+    }
+
+    /// Adds a restricted name.
+    pub fn with_restricted_name(mut self, name: &str) -> ValidationConfig {
+        self.restricted_names.push(name.to_string());
+        self
+    }
+
+    /// Removes all restricted names.
+    pub fn without_restricted_names(mut self) -> ValidationConfig {
+        self.restricted_names.clear();
+        self
+    }
+
+    /// Sets whether restricted names are compared case-sensitively.
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> ValidationConfig {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Sets the characters values may contain.
+    pub fn with_character_policy(mut self, character_policy: CharacterPolicy) -> ValidationConfig {
+        self.character_policy = character_policy;
+        self
+    }
+
+    /// Sets the unicode checks values must pass.
+    ///
+    /// No unicode checks are applied by default.
+    #[cfg(feature = "unicode")]
+    pub fn with_unicode_policy(mut self, unicode_policy: UnicodePolicy) -> ValidationConfig {
+        self.unicode_policy = Some(unicode_policy);
+        self
+    }
+
+    /// Returns the maximum length.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns how the length is measured.
+    pub fn length_unit(&self) -> LengthUnit {
+        self.length_unit
+    }
+
+    /// Returns the restricted names.
+    pub fn restricted_names(&self) -> impl Iterator<Item = &str> {
+        self.restricted_names.iter().map(|x| x.as_str())
+    }
+
+    /// Returns `true` if restricted names are compared case-sensitively.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Returns the characters values may contain.
+    pub fn character_policy(&self) -> CharacterPolicy {
+        self.character_policy
+    }
+
+    /// Returns the unicode checks values must pass.
+    #[cfg(feature = "unicode")]
+    pub fn unicode_policy(&self) -> Option<&UnicodePolicy> {
+        self.unicode_policy.as_ref()
+    }
+
+    /// Measures the length of a value in the configured unit.
+    pub fn length_of(&self, value: &str) -> usize {
+        match self.length_unit {
+            LengthUnit::Bytes => value.len(),
+            LengthUnit::Chars => value.chars().count(),
+        }
+    }
+
+    /// Checks if a value is one of the restricted names.
+    pub fn is_restricted(&self, value: &str) -> bool {
+        self.restricted_names().any(|name| {
+            if self.case_sensitive {
+                name == value
+            } else {
+                name.eq_ignore_ascii_case(value)
+            }
+        })
+    }
+
+    fn check(&self, value: &str) -> Result<(), Violation> {
+        let length = self.length_of(value);
+        if length > self.max_length {
+            return Err(Violation::TooLong {
+                length,
+                limit: self.max_length,
+            });
+        } else if self.is_restricted(value) {
+            return Err(Violation::RestrictedName);
+        } else if let Some((offset, character)) = self.character_policy.find_disallowed(value) {
+            return Err(Violation::BadCharacters { character, offset });
+        }
+        #[cfg(feature = "unicode")]
+        {
+            if let Some(ref policy) = self.unicode_policy {
+                policy.check(value).map_err(Violation::UnsafeUnicode)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Given a string checks if the release is valid under the given rules.
+pub fn validate_release_with(
+    release: &str,
+    config: &ValidationConfig,
+) -> Result<(), InvalidRelease> {
+    config.check(release).map_err(|violation| match violation {
+        Violation::TooLong { length, limit } => InvalidRelease::TooLong { length, limit },
+        Violation::RestrictedName => InvalidRelease::RestrictedName,
+        Violation::BadCharacters { character, offset } => {
+            InvalidRelease::BadCharacters { character, offset }
+        }
+        Violation::UnsafeUnicode(issue) => InvalidRelease::UnsafeUnicode(issue),
+    })
+}
+
+/// Given a string checks if the environment name is valid under the given
+/// rules.
+pub fn validate_environment_with(
+    environment: &str,
+    config: &ValidationConfig,
+) -> Result<(), InvalidEnvironment> {
+    config
+        .check(environment)
+        .map_err(|violation| match violation {
+            Violation::TooLong { length, limit } => InvalidEnvironment::TooLong { length, limit },
+            Violation::RestrictedName => InvalidEnvironment::RestrictedName,
+            Violation::BadCharacters { character, offset } => {
+                InvalidEnvironment::BadCharacters { character, offset }
+            }
+            Violation::UnsafeUnicode(issue) => InvalidEnvironment::UnsafeUnicode(issue),
+        })
+}
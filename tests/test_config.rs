@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+
+use sentry_release_parser::{
+    PackageMatcher, Parser, ParserConfig, Release, Version, VersionScheme,
+};
+use similar_asserts::assert_eq;
+
+fn config() -> ParserConfig {
+    ParserConfig::new()
+        .with_rule(PackageMatcher::Prefix("com.acme.".into()), "default")
+        .with_rule(PackageMatcher::Exact("acme-api".into()), "pep440")
+        .with_rule(PackageMatcher::Glob("@acme-*".into()), "semver")
+}
+
+#[test]
+fn test_package_matcher() {
+    assert!(PackageMatcher::Exact("foo".into()).matches("foo"));
+    assert!(!PackageMatcher::Exact("foo".into()).matches("foobar"));
+    assert!(PackageMatcher::Prefix("foo".into()).matches("foobar"));
+    assert!(!PackageMatcher::Prefix("foo".into()).matches("barfoo"));
+    assert!(PackageMatcher::Glob("@acme-*".into()).matches("@acme-web"));
+    assert!(!PackageMatcher::Glob("@acme-*".into()).matches("@other-web"));
+}
+
+#[test]
+fn test_schemes_by_package() {
+    let parser = Parser::from_config(&config()).unwrap();
+    let scheme = |release| Release::parse_with(release, &parser).unwrap().scheme();
+
+    assert_eq!(scheme("com.acme.android@1.0+20200101100"), Some("default"));
+    assert_eq!(scheme("acme-api@1.0.post1"), Some("pep440"));
+    assert_eq!(scheme("acme-api@1.0.0.0.1"), None);
+    assert_eq!(scheme("@acme-web@1.0.0-rc.1"), Some("semver"));
+    assert_eq!(scheme("@acme-web@1.0"), None);
+    assert_eq!(scheme("other@1.0"), Some("default"));
+
+    let parser = Parser::from_config(&config().with_fallback("semver")).unwrap();
+    let scheme = |release| Release::parse_with(release, &parser).unwrap().scheme();
+    assert_eq!(scheme("other@1.0"), None);
+    assert_eq!(scheme("other@1.0.0"), Some("semver"));
+}
+
+#[test]
+fn test_pep440_scheme() {
+    let parser = Parser::from_config(&config()).unwrap();
+    let release = Release::parse_with("acme-api@1.2.0.post1+ubuntu.1", &parser).unwrap();
+    let version = release.version().unwrap();
+    assert_eq!(version.triple(), (1, 2, 0));
+    assert_eq!(version.pre(), None);
+    assert_eq!(version.build_code(), Some("ubuntu.1"));
+    let pre = |release| {
+        Release::parse_with(release, &parser)
+            .unwrap()
+            .version()
+            .unwrap()
+            .pre()
+    };
+    assert_eq!(pre("acme-api@1.0rc1.post2"), Some("rc1"));
+    assert_eq!(pre("acme-api@1.0.dev1"), Some("dev1"));
+    assert_eq!(pre("acme-api@1.0a1.dev1"), Some("a1.dev1"));
+    assert_eq!(pre("acme-api@1.0a1_dev1"), Some("a"));
+    let release = Release::parse_with("acme-api@1.0+ubuntu_1", &parser).unwrap();
+    assert_eq!(release.scheme(), Some("pep440"));
+    assert_eq!(release.version().unwrap().build_code(), None);
+
+    // releases are ordered by the scheme that parsed their versions
+    let mut releases: Vec<_> = [
+        "acme-api@1.0.post10",
+        "acme-api@1.0",
+        "acme-api@1!0.5",
+        "acme-api@1.0a1",
+        "acme-api@1.0.post9",
+        "acme-api@2.0",
+        "acme-api@1.0a1.dev1",
+        "acme-api@1.0.dev1",
+    ]
+    .iter()
+    .map(|r| Release::parse_with(r, &parser).unwrap())
+    .collect();
+    releases.sort();
+    assert_eq!(
+        releases.iter().map(|r| r.raw()).collect::<Vec<_>>(),
+        [
+            "acme-api@1.0.dev1",
+            "acme-api@1.0a1.dev1",
+            "acme-api@1.0a1",
+            "acme-api@1.0",
+            "acme-api@1.0.post9",
+            "acme-api@1.0.post10",
+            "acme-api@2.0",
+            "acme-api@1!0.5",
+        ]
+    );
+    assert_eq!(
+        releases[5].cmp_version(&releases[4]),
+        Some(Ordering::Greater)
+    );
+
+    // versions of different schemes are not compared
+    let default = Release::parse("acme-api@1.0").unwrap();
+    assert_eq!(default.cmp_version(&releases[3]), None);
+
+    let compare = |a, b| {
+        parser.compare(
+            &Release::parse_with(a, &parser).unwrap(),
+            &Release::parse_with(b, &parser).unwrap(),
+        )
+    };
+    assert_eq!(
+        compare("acme-api@1.0.post1", "acme-api@1.0"),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        compare("acme-api@1.0.dev1", "acme-api@1.0a1"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare("acme-api@1.0", "acme-api@1.0.0"),
+        Some(Ordering::Equal)
+    );
+}
+
+#[test]
+fn test_semver_scheme() {
+    let parser = Parser::from_config(&config()).unwrap();
+    let compare = |a, b| {
+        parser.compare(
+            &Release::parse_with(a, &parser).unwrap(),
+            &Release::parse_with(b, &parser).unwrap(),
+        )
+    };
+    assert_eq!(
+        compare("@acme-web@1.0.0-rc.2", "@acme-web@1.0.0-rc.10"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare("@acme-web@1.0.0-rc.1", "@acme-web@1.0.0"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare("@acme-web@1.0.0+1", "@acme-web@1.0.0+2"),
+        Some(Ordering::Equal)
+    );
+}
+
+#[test]
+fn test_unknown_scheme() {
+    let err = Parser::from_config(&ParserConfig::new().with_fallback("train")).unwrap_err();
+    assert_eq!(err.scheme(), "train");
+    assert_eq!(
+        err.to_string(),
+        "invalid parser config: unknown scheme \"train\""
+    );
+}
+
+#[test]
+fn test_custom_scheme_in_config() {
+    struct TrainScheme;
+
+    impl VersionScheme for TrainScheme {
+        fn name(&self) -> &'static str {
+            "train"
+        }
+
+        fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+            let train = version.strip_prefix("train-")?;
+            Version::from_parts(version, (train, None, None, None), None, None).ok()
+        }
+    }
+
+    let mut parser = Parser::new();
+    parser.register(TrainScheme);
+    parser
+        .configure(&ParserConfig::new().with_rule(PackageMatcher::Exact("mobile".into()), "train"))
+        .unwrap();
+    let release = Release::parse_with("mobile@train-42", &parser).unwrap();
+    assert_eq!(release.scheme(), Some("train"));
+    assert_eq!(release.version().unwrap().major(), 42);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_deserialize() {
+    let config: ParserConfig = serde_json::from_str(
+        r#"{
+            "rules": [
+                {"match": {"prefix": "com.acme."}, "scheme": "default"},
+                {"match": {"exact": "acme-api"}, "scheme": "pep440"},
+                {"match": {"glob": "@acme-*"}, "scheme": "semver"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(config, self::config());
+
+    let config: ParserConfig = serde_json::from_str(r#"{"fallback": "semver"}"#).unwrap();
+    assert_eq!(config.fallback(), Some("semver"));
+    assert!(config.rules().is_empty());
+}
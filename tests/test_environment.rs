@@ -0,0 +1,99 @@
+use sentry_release_parser::{Environment, EnvironmentTier, InvalidEnvironment, TierAliases};
+use similar_asserts::assert_eq;
+
+#[test]
+fn test_parse() {
+    let environment = Environment::parse("  Production ").unwrap();
+    assert_eq!(environment.raw(), "  Production ");
+    assert_eq!(environment.normalized(), "production");
+    assert_eq!(environment.tier(), EnvironmentTier::Production);
+    assert_eq!(environment.qualifier(), None);
+    assert!(environment.is_production());
+
+    assert_eq!(
+        Environment::parse("none"),
+        Err(InvalidEnvironment::RestrictedName)
+    );
+    assert_eq!(
+        Environment::parse(" none "),
+        Err(InvalidEnvironment::RestrictedName)
+    );
+    assert_eq!(
+        Environment::parse(" foo/bar"),
+        Err(InvalidEnvironment::BadCharacters {
+            character: '/',
+            offset: 4
+        })
+    );
+    assert_eq!(
+        Environment::parse("foo\nbar"),
+        Err(InvalidEnvironment::BadCharacters {
+            character: '\n',
+            offset: 3
+        })
+    );
+}
+
+#[test]
+fn test_tiers() {
+    let cases = [
+        ("prod", EnvironmentTier::Production),
+        ("PRD", EnvironmentTier::Production),
+        ("live", EnvironmentTier::Production),
+        ("stg", EnvironmentTier::Staging),
+        ("pre-prod", EnvironmentTier::Staging),
+        ("dev", EnvironmentTier::Development),
+        ("QA", EnvironmentTier::Testing),
+        ("preview", EnvironmentTier::Preview),
+        ("eu-central", EnvironmentTier::Unknown),
+        ("production2", EnvironmentTier::Unknown),
+    ];
+    for &(name, tier) in cases.iter() {
+        assert_eq!(Environment::parse(name).unwrap().tier(), tier, "{}", name);
+    }
+}
+
+#[test]
+fn test_compound() {
+    let cases = [
+        (
+            "prod-us-east-1",
+            EnvironmentTier::Production,
+            Some("us-east-1"),
+        ),
+        ("staging_eu", EnvironmentTier::Staging, Some("eu")),
+        ("pre-prod-eu", EnvironmentTier::Staging, Some("eu")),
+        (
+            "us-east-1-prod",
+            EnvironmentTier::Production,
+            Some("us-east-1"),
+        ),
+        (
+            "cluster7.dev",
+            EnvironmentTier::Development,
+            Some("cluster7"),
+        ),
+        ("prod-", EnvironmentTier::Production, None),
+    ];
+    for &(name, tier, qualifier) in cases.iter() {
+        let environment = Environment::parse(name).unwrap();
+        assert_eq!(environment.tier(), tier, "{}", name);
+        assert_eq!(environment.qualifier(), qualifier, "{}", name);
+    }
+}
+
+#[test]
+fn test_custom_aliases() {
+    let aliases = TierAliases::empty().with_alias("Blue", EnvironmentTier::Production);
+    assert_eq!(aliases.tier("blue"), Some(EnvironmentTier::Production));
+    let environment = Environment::parse_with("blue-eu", &aliases).unwrap();
+    assert_eq!(environment.tier(), EnvironmentTier::Production);
+    assert_eq!(environment.qualifier(), Some("eu"));
+    assert_eq!(
+        Environment::parse_with("prod", &aliases).unwrap().tier(),
+        EnvironmentTier::Unknown
+    );
+
+    let aliases = TierAliases::new().with_alias("live", EnvironmentTier::Preview);
+    assert_eq!(aliases.tier("live"), Some(EnvironmentTier::Preview));
+}
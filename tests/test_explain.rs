@@ -0,0 +1,118 @@
+use sentry_release_parser::{InvalidRelease, Release, VersionFailure};
+use similar_asserts::assert_eq;
+
+fn failure(release: &str) -> Option<VersionFailure> {
+    Release::explain(release).version_failure()
+}
+
+#[test]
+fn test_version_failures() {
+    assert_eq!(failure("myapp@1.0.0"), None);
+    assert_eq!(
+        failure("myapp@1.0.0.0.1"),
+        Some(VersionFailure::TooManyComponents)
+    );
+    assert_eq!(failure("myapp@v1.0"), Some(VersionFailure::NoLeadingNumber));
+    assert_eq!(
+        failure("myapp@1.0-rc.01"),
+        Some(VersionFailure::InvalidPrerelease)
+    );
+    assert_eq!(
+        failure("myapp@1.0A"),
+        Some(VersionFailure::InvalidPrerelease)
+    );
+    assert_eq!(
+        failure("myapp@1.0+a..b"),
+        Some(VersionFailure::InvalidBuildCode)
+    );
+    assert_eq!(
+        failure("myapp@1a1"),
+        Some(VersionFailure::SingleComponentPrerelease)
+    );
+    assert_eq!(failure("myapp@1-a1"), None);
+}
+
+#[test]
+fn test_hashes() {
+    let explanation = Release::explain("myapp@a86d127c4b2f");
+    assert_eq!(explanation.package(), Some("myapp"));
+    assert_eq!(explanation.version_raw(), Some("a86d127c4b2f"));
+    assert!(explanation.hash_check().unwrap().is_hash());
+    assert!(!explanation.version_attempted());
+    assert!(explanation.version().is_none());
+
+    let explanation = Release::explain("myapp@a86d127c4b2");
+    let hash_check = explanation.hash_check().unwrap();
+    assert_eq!(hash_check.length(), 11);
+    assert!(hash_check.is_hex());
+    assert!(!hash_check.has_hash_length());
+    assert_eq!(
+        explanation.version_failure(),
+        Some(VersionFailure::NoLeadingNumber)
+    );
+}
+
+#[test]
+fn test_without_package() {
+    let explanation = Release::explain(" 1.0.0\n");
+    assert!(explanation.was_trimmed());
+    assert_eq!(explanation.trimmed(), "1.0.0");
+    assert_eq!(explanation.package(), None);
+    assert!(!explanation.version_attempted());
+    assert_eq!(explanation.release().unwrap().raw(), "1.0.0");
+}
+
+#[test]
+fn test_invalid() {
+    let explanation = Release::explain("latest");
+    assert_eq!(
+        explanation.validation_error(),
+        Some(&InvalidRelease::RestrictedName)
+    );
+    assert!(explanation.release().is_none());
+    assert!(explanation.hash_check().is_none());
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(
+        Release::explain("  myapp@1.0.0.0.1").to_string(),
+        "\
+input: \"  myapp@1.0.0.0.1\"
+trimmed: \"myapp@1.0.0.0.1\"
+validation: ok
+split: package \"myapp\", version \"1.0.0.0.1\"
+hash: not a build hash (not hexadecimal)
+version: failed (more than four numeric components)
+kind: opaque"
+    );
+    assert_eq!(
+        Release::explain("a86d127c4b2fa86d127c4b2f").to_string(),
+        "\
+input: \"a86d127c4b2fa86d127c4b2f\"
+trimmed: nothing to trim
+validation: ok
+split: no package (no @ separator)
+hash: not a build hash (length 24 is not one of 12, 16, 20, 32, 40, 64)
+version: not parsed (releases need a package)
+kind: opaque"
+    );
+    assert_eq!(
+        Release::explain("foo@1.0+a86d127c4b2f").to_string(),
+        "\
+input: \"foo@1.0+a86d127c4b2f\"
+trimmed: nothing to trim
+validation: ok
+split: package \"foo\", version \"1.0+a86d127c4b2f\"
+hash: not a build hash (not hexadecimal)
+version: parsed 1.0+a86d127c4b2f
+kind: versioned_with_hash"
+    );
+    assert_eq!(
+        Release::explain("..").to_string(),
+        "\
+input: \"..\"
+trimmed: nothing to trim
+validation: invalid release: restricted release name"
+    );
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use sentry_release_parser::{FlavorConfig, FlavorLocation, Release};
+use similar_asserts::assert_eq;
+
+#[test]
+fn test_package_flavor() {
+    let release = Release::parse("com.acme.app.debug@1.2.3").unwrap();
+    let flavored = release.flavored();
+    assert_eq!(flavored.flavor(), Some("debug"));
+    assert_eq!(flavored.location(), Some(FlavorLocation::Package));
+    assert_eq!(flavored.base_package(), Some("com.acme.app"));
+    assert_eq!(flavored.base_version().unwrap().raw(), "1.2.3");
+
+    let flavored = Release::parse("acme-api-Staging@1.0").unwrap().flavored();
+    assert_eq!(flavored.flavor(), Some("Staging"));
+    assert_eq!(flavored.base_package(), Some("acme-api"));
+
+    // the flavor needs a separator and a base
+    assert_eq!(
+        Release::parse("acme-qa@1.0").unwrap().flavored().flavor(),
+        Some("qa")
+    );
+    assert_eq!(
+        Release::parse("acmeqa@1.0").unwrap().flavored().flavor(),
+        None
+    );
+    assert_eq!(Release::parse("qa@1.0").unwrap().flavored().flavor(), None);
+}
+
+#[test]
+fn test_version_flavor() {
+    let cases = [
+        ("com.acme.app@1.2.3-debug", "1.2.3", "debug"),
+        ("com.acme.app@1.2.3-staging+45", "1.2.3+45", "staging"),
+        ("com.acme.app@1.2.3-rc1.internal", "1.2.3-rc1", "internal"),
+        ("com.acme.app@1.2.3-rc1-QA", "1.2.3-rc1", "QA"),
+        ("com.acme.app@1.2debug", "1.2", "debug"),
+    ];
+    for &(release, base_version, flavor) in cases.iter() {
+        let flavored = Release::parse(release).unwrap().flavored();
+        assert_eq!(flavored.flavor(), Some(flavor), "{}", release);
+        assert_eq!(flavored.location(), Some(FlavorLocation::Version));
+        assert_eq!(flavored.base_package(), Some("com.acme.app"));
+        assert_eq!(flavored.base_version().unwrap().raw(), base_version);
+    }
+
+    let flavored = Release::parse("com.acme.app@1.2.3-rc1").unwrap().flavored();
+    assert_eq!(flavored.flavor(), None);
+    assert_eq!(flavored.location(), None);
+    assert_eq!(flavored.base_version().unwrap().raw(), "1.2.3-rc1");
+    assert_eq!(
+        Release::parse("com.acme.app@1.2.3-notqa")
+            .unwrap()
+            .flavored()
+            .flavor(),
+        None
+    );
+}
+
+#[test]
+fn test_config() {
+    let config = FlavorConfig::empty().with_suffix("beta");
+    assert_eq!(config.suffixes().collect::<Vec<_>>(), ["beta"]);
+    let release = Release::parse("com.acme.app@1.2.3-beta").unwrap();
+    assert_eq!(config.detect(&release).flavor(), Some("beta"));
+    assert_eq!(release.flavored().flavor(), None);
+    assert_eq!(
+        FlavorConfig::new().suffixes().collect::<Vec<_>>(),
+        ["debug", "staging", "internal", "qa"]
+    );
+}
+
+#[test]
+fn test_cmp_ignoring_flavor() {
+    let flavored = |r| Release::parse(r).unwrap().flavored();
+    let release = flavored("com.acme.app@1.2.3");
+    assert_eq!(
+        release.cmp_ignoring_flavor(&flavored("com.acme.app@1.2.3-debug")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        release.cmp_ignoring_flavor(&flavored("com.acme.app.debug@1.2.3")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        release.cmp_ignoring_flavor(&flavored("com.acme.app@1.2.2-staging")),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        release.cmp_ignoring_flavor(&flavored("com.acme.other@1.2.3")),
+        None
+    );
+}
@@ -0,0 +1,75 @@
+use sentry_release_parser::{lint_release, lint_releases, LintCode, LintSeverity};
+use similar_asserts::assert_eq;
+
+fn codes(release: &str) -> Vec<LintCode> {
+    lint_release(release).iter().map(|w| w.code()).collect()
+}
+
+#[test]
+fn test_clean() {
+    assert_eq!(codes("org.example.FooApp@1.0rc1+20200101100"), []);
+    assert_eq!(codes("myapp@2.0.0-beta.1"), []);
+    assert_eq!(codes("myapp@1.0.0-dev"), []);
+    assert_eq!(codes("myapp@1.2.3-test"), []);
+    assert_eq!(codes("myapp@a86d127c4b2f97e4ef5ef7c0ba0d4d4b2e4ac1e5"), []);
+}
+
+#[test]
+fn test_warnings() {
+    assert_eq!(
+        codes("a86d127c4b2f97e4ef5ef7c0ba0d4d4b2e4ac1e5"),
+        [LintCode::BareHash]
+    );
+    assert_eq!(codes(" myapp@1.0 "), [LintCode::SurroundingWhitespace]);
+    assert_eq!(codes("myapp@"), [LintCode::EmptyVersion]);
+    assert_eq!(codes("myapp@--"), [LintCode::EmptyVersion]);
+    assert_eq!(
+        codes("myapp@1.0-production"),
+        [LintCode::EnvironmentPrerelease]
+    );
+    assert_eq!(
+        codes("myapp@1.0-STAGING"),
+        [LintCode::EnvironmentPrerelease]
+    );
+    assert_eq!(codes("my app@1.0"), [LintCode::PackageWhitespace]);
+    assert_eq!(
+        codes(" foo/bar "),
+        [LintCode::SurroundingWhitespace, LintCode::InvalidRelease]
+    );
+}
+
+#[test]
+fn test_warning_details() {
+    let warnings = lint_release("myapp@1.0-prod");
+    assert_eq!(warnings[0].severity(), LintSeverity::Warning);
+    assert_eq!(
+        warnings[0].message(),
+        "pre-release \"prod\" looks like a production environment"
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "warning[environment_prerelease]: pre-release \"prod\" looks like a production environment"
+    );
+
+    let warnings = lint_release("latest");
+    assert_eq!(warnings[0].severity(), LintSeverity::Error);
+    assert_eq!(
+        warnings[0].to_string(),
+        "error[invalid_release]: invalid release: restricted release name"
+    );
+}
+
+#[test]
+fn test_case_conflicts() {
+    let warnings = lint_releases(vec!["myapp@1.0", "MyApp@1.0", "myapp@1.0", "other@1.0"]);
+    assert_eq!(warnings.len(), 4);
+    assert_eq!(warnings[0], []);
+    assert_eq!(warnings[1].len(), 1);
+    assert_eq!(warnings[1][0].code(), LintCode::CaseConflict);
+    assert_eq!(
+        warnings[1][0].message(),
+        "release differs from \"myapp@1.0\" only in case"
+    );
+    assert_eq!(warnings[2], []);
+    assert_eq!(warnings[3], []);
+}
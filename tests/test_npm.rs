@@ -0,0 +1,120 @@
+use sentry_release_parser::{InvalidRange, NpmRange, Version};
+use similar_asserts::assert_eq;
+
+fn desugar(range: &str) -> String {
+    NpmRange::parse(range).unwrap().to_string()
+}
+
+fn matches(range: &str, version: &str) -> bool {
+    NpmRange::parse(range)
+        .unwrap()
+        .matches(&Version::parse(version).unwrap())
+}
+
+#[test]
+fn test_desugar() {
+    assert_eq!(desugar("1.0.0 - 2.0.0"), ">=1.0.0 <=2.0.0");
+    assert_eq!(desugar("1.0.0\t-\t2.0.0"), ">=1.0.0 <=2.0.0");
+    assert_eq!(desugar("1.2 - 2.3.4"), ">=1.2.0 <=2.3.4");
+    assert_eq!(desugar("1.2.3 - 2.3"), ">=1.2.3 <2.4.0-0");
+    assert_eq!(desugar("1.2.3 - 2"), ">=1.2.3 <3.0.0-0");
+    assert_eq!(desugar("1.0.0"), "1.0.0");
+    assert_eq!(desugar("=v1.0.0"), "1.0.0");
+    assert_eq!(desugar(">=*"), "*");
+    assert_eq!(desugar(""), "*");
+    assert_eq!(desugar("*"), "*");
+    assert_eq!(desugar(">*"), "<0.0.0-0");
+    assert_eq!(desugar(">=1.0.0"), ">=1.0.0");
+    assert_eq!(desugar(">= 1.0.0"), ">=1.0.0");
+    assert_eq!(desugar(">1.0.0"), ">1.0.0");
+    assert_eq!(desugar("<=2.0.0"), "<=2.0.0");
+    assert_eq!(desugar("<2.0.0"), "<2.0.0");
+    assert_eq!(desugar(">1"), ">=2.0.0");
+    assert_eq!(desugar(">1.2"), ">=1.3.0");
+    assert_eq!(desugar("<1.2"), "<1.2.0-0");
+    assert_eq!(desugar("<=1.2"), "<1.3.0-0");
+    assert_eq!(desugar(">=1.2"), ">=1.2.0");
+    assert_eq!(desugar("1.x"), ">=1.0.0 <2.0.0-0");
+    assert_eq!(desugar("1.2.x"), ">=1.2.0 <1.3.0-0");
+    assert_eq!(desugar("1.2"), ">=1.2.0 <1.3.0-0");
+    assert_eq!(desugar("1.X.3"), ">=1.0.0 <2.0.0-0");
+    assert_eq!(desugar("~1.2.3"), ">=1.2.3 <1.3.0-0");
+    assert_eq!(desugar("~1.2"), ">=1.2.0 <1.3.0-0");
+    assert_eq!(desugar("~1"), ">=1.0.0 <2.0.0-0");
+    assert_eq!(desugar("~>1.2.3"), ">=1.2.3 <1.3.0-0");
+    assert_eq!(desugar("~ 1.2.3-beta.2"), ">=1.2.3-beta.2 <1.3.0-0");
+    assert_eq!(desugar("^1.2.3"), ">=1.2.3 <2.0.0-0");
+    assert_eq!(desugar("^0.2.3"), ">=0.2.3 <0.3.0-0");
+    assert_eq!(desugar("^0.0.3"), ">=0.0.3 <0.0.4-0");
+    assert_eq!(desugar("^1.2.3-beta.2"), ">=1.2.3-beta.2 <2.0.0-0");
+    assert_eq!(desugar("^1.2.x"), ">=1.2.0 <2.0.0-0");
+    assert_eq!(desugar("^0.0.x"), ">=0.0.0 <0.1.0-0");
+    assert_eq!(desugar("^0.x"), ">=0.0.0 <1.0.0-0");
+    assert_eq!(
+        desugar(">=1.0.0 <2.0.0 || >=3.0.0"),
+        ">=1.0.0 <2.0.0 || >=3.0.0"
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(NpmRange::parse("1.2.3.4"), Err(InvalidRange));
+    assert_eq!(NpmRange::parse(">=a.b.c"), Err(InvalidRange));
+    assert_eq!(NpmRange::parse("^1.2.3 || blah"), Err(InvalidRange));
+    assert_eq!(NpmRange::parse(">=99999999999999999999"), Err(InvalidRange));
+    assert_eq!(NpmRange::parse("^18446744073709551615"), Err(InvalidRange));
+    assert_eq!(NpmRange::parse("18446744073709551615.x"), Err(InvalidRange));
+    assert_eq!(
+        NpmRange::parse("~1.18446744073709551615"),
+        Err(InvalidRange)
+    );
+    assert_eq!(
+        NpmRange::parse("1.2.3 - 18446744073709551615.x"),
+        Err(InvalidRange)
+    );
+}
+
+#[test]
+fn test_matches() {
+    assert!(matches("^3.2.0", "3.2.1"));
+    assert!(matches("^3.2.0", "3.9"));
+    assert!(!matches("^3.2.0", "4.0.0"));
+    assert!(!matches("^3.2.0", "3.1.9"));
+    assert!(matches("~3.2", "3.2.9"));
+    assert!(!matches("~3.2", "3.3.0"));
+    assert!(matches("3.x", "3.99.1"));
+    assert!(!matches("3.x", "4.0.0"));
+    assert!(matches("1.2.3 - 2.3.4", "2.3.4"));
+    assert!(!matches("1.2.3 - 2.3.4", "2.3.5"));
+    assert!(matches(">=1.0.0 <2.0.0 || >=3.0.0", "1.5.0"));
+    assert!(!matches(">=1.0.0 <2.0.0 || >=3.0.0", "2.5.0"));
+    assert!(matches(">=1.0.0 <2.0.0 || >=3.0.0", "3.0.0"));
+    assert!(matches("*", "0.0.1"));
+    assert!(matches("1.0.0", "1.0.0+build"));
+    assert!(matches("1.0.0", "1.0"));
+    assert!(!matches("1.0.0", "1.0.1"));
+
+    // revisions compare as fourth component
+    assert!(matches(">1.2.3", "1.2.3.4"));
+    assert!(matches("~1.2.3", "1.2.3.4"));
+    assert!(!matches("<=1.2.3", "1.2.3.4"));
+}
+
+#[test]
+fn test_prerelease_matching() {
+    assert!(!matches("*", "1.0.0-rc1"));
+    assert!(!matches("^1.2.0", "1.3.0-rc1"));
+    assert!(!matches("^1.2.0", "2.0.0-rc1"));
+    assert!(matches("^1.2.3-beta.2", "1.2.3-beta.4"));
+    assert!(!matches("^1.2.3-beta.2", "1.2.4-beta.2"));
+    assert!(matches(">=1.2.5-beta", "1.2.5-rc1"));
+    assert!(matches(">=1.2.5-beta", "1.2.5rc1"));
+    assert!(!matches(">=1.2.5-beta", "1.2.5-alpha"));
+    assert!(matches("~1.2.3-beta.2", "1.2.3-beta.10"));
+    assert!(!matches("~1.2.3-beta.2", "1.2.3-beta.1"));
+    assert!(matches("1.2.3-alpha - 2.0.0", "1.2.3-beta"));
+    assert!(!matches("1.2.3 - 2.0.0-rc1", "1.5.0-rc1"));
+    assert!(matches("1.2.3 - 2.0.0-rc1", "2.0.0-alpha"));
+    assert!(matches("1.0.0-rc1", "1.0.0-rc1"));
+    assert!(!matches("1.0.0-rc1", "1.0.0-rc2"));
+}
@@ -0,0 +1,105 @@
+use sentry_release_parser::{InvalidPackage, Package, PackageKind, Release};
+use similar_asserts::assert_eq;
+
+#[test]
+fn test_kinds() {
+    assert_eq!(
+        Package::parse("@sentry/browser").kind(),
+        PackageKind::NpmScoped
+    );
+    assert_eq!(
+        Package::parse("org.example.FooApp").kind(),
+        PackageKind::ReverseDns
+    );
+    assert_eq!(Package::parse("com.acme").kind(), PackageKind::ReverseDns);
+    assert_eq!(Package::parse("lodash.debounce").kind(), PackageKind::Plain);
+    assert_eq!(Package::parse("sentry-api").kind(), PackageKind::Plain);
+    assert_eq!(Package::parse("@sentry-api").kind(), PackageKind::Plain);
+}
+
+#[test]
+fn test_accessors() {
+    let package = Package::parse("@sentry/browser");
+    assert_eq!(package.scope(), Some("sentry"));
+    assert_eq!(package.segments(), ["sentry", "browser"]);
+    assert_eq!(package.display_name(), "browser");
+
+    let package = Package::parse("org.example.FooApp");
+    assert_eq!(package.scope(), Some("org.example"));
+    assert_eq!(package.segments(), ["org", "example", "FooApp"]);
+    assert_eq!(package.display_name(), "FooApp");
+
+    let package = Package::parse("sentry-api");
+    assert_eq!(package.scope(), None);
+    assert_eq!(package.segments(), ["sentry-api"]);
+    assert_eq!(package.display_name(), "sentry-api");
+    assert_eq!(package.to_string(), "sentry-api");
+}
+
+#[test]
+fn test_validate() {
+    assert_eq!(Package::parse("@sentry/browser").validate(), Ok(()));
+    assert_eq!(
+        Package::parse("@Sentry/browser").validate(),
+        Err(InvalidPackage::Uppercase)
+    );
+    assert_eq!(
+        Package::parse("@sentry/_browser").validate(),
+        Err(InvalidPackage::InvalidSegmentStart)
+    );
+    assert_eq!(
+        Package::parse("@sentry/").validate(),
+        Err(InvalidPackage::EmptySegment)
+    );
+    assert_eq!(
+        Package::parse("@sentry/bro wser").validate(),
+        Err(InvalidPackage::BadCharacters)
+    );
+    assert_eq!(
+        Package::parse(&format!("@sentry/{}", "a".repeat(210))).validate(),
+        Err(InvalidPackage::TooLong)
+    );
+
+    assert_eq!(Package::parse("org.example.Foo_App-2").validate(), Ok(()));
+    assert_eq!(
+        Package::parse("org.example.2App").validate(),
+        Err(InvalidPackage::InvalidSegmentStart)
+    );
+    assert_eq!(
+        Package::parse("org..example").validate(),
+        Err(InvalidPackage::EmptySegment)
+    );
+    assert_eq!(
+        Package::parse("org.exa+mple.app").validate(),
+        Err(InvalidPackage::BadCharacters)
+    );
+
+    assert_eq!(Package::parse("sentry-api").validate(), Ok(()));
+    assert_eq!(
+        Package::parse("sentry api").validate(),
+        Err(InvalidPackage::BadCharacters)
+    );
+    assert_eq!(Package::parse("").validate(), Err(InvalidPackage::Empty));
+    assert_eq!(
+        InvalidPackage::Uppercase.to_string(),
+        "invalid package: uppercase characters in package name"
+    );
+}
+
+#[test]
+fn test_eq_ignore_case() {
+    let a = Package::parse("org.example.FooApp");
+    assert!(a.eq_ignore_case(&Package::parse("org.example.fooapp")));
+    assert!(!a.eq_ignore_case(&Package::parse("org.example.BarApp")));
+    assert_ne!(a, Package::parse("org.example.fooapp"));
+}
+
+#[test]
+fn test_release_package_info() {
+    let release = Release::parse("org.example.FooApp@1.0").unwrap();
+    assert_eq!(
+        release.package_info(),
+        Some(Package::parse("org.example.FooApp"))
+    );
+    assert_eq!(Release::parse("1.0").unwrap().package_info(), None);
+}
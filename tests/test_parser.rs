@@ -1,4 +1,8 @@
-use sentry_release_parser::{InvalidRelease, Release};
+use std::cmp::Ordering;
+
+use sentry_release_parser::{
+    InvalidRelease, OwnedVersion, Release, ReleaseKind, Version, VersionChange,
+};
 use similar_asserts::assert_eq;
 
 #[test]
@@ -211,7 +215,10 @@ fn test_invalid_releases() {
     assert_eq!(Release::parse("  foo   ").unwrap().version_raw(), "foo");
     assert_eq!(
         Release::parse("foo/bar"),
-        Err(InvalidRelease::BadCharacters)
+        Err(InvalidRelease::BadCharacters {
+            character: '/',
+            offset: 3
+        })
     );
     assert_eq!(Release::parse("."), Err(InvalidRelease::RestrictedName));
     assert_eq!(Release::parse(".."), Err(InvalidRelease::RestrictedName));
@@ -220,3 +227,193 @@ fn test_invalid_releases() {
         Err(InvalidRelease::RestrictedName)
     );
 }
+
+#[test]
+fn test_cmp_version() {
+    let release = |s| Release::parse(s).unwrap();
+    assert_eq!(
+        release("foo@1.0.0").cmp_version(&release("foo@1.0")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        release("foo@1.0.0").cmp_version(&release("foo@1.0.0-rc1")),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        release("foo@1.0.0").cmp_version(&release("foo@2.0.0")),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        release("foo@1.0.0").cmp_version(&release("bar@1.0.0")),
+        None
+    );
+    assert_eq!(release("foo@1.0.0").cmp_version(&release("1.0.0")), None);
+    assert_eq!(release("foo@1.0.0").cmp_version(&release("foo@abc")), None);
+    assert_eq!(
+        release("foo@1.0.0").cmp_version(&release("foo@a86d127c4b2f")),
+        None
+    );
+}
+
+#[test]
+fn test_release_ordering() {
+    let mut releases = vec![
+        "whatever",
+        "foo@a86d127c4b2f",
+        "foo@2.0.0",
+        "085240e737828d8326719bf97730188e927e49ca",
+        "bar@1.0.0",
+        "foo@1.0.0-rc1",
+        "foo@1.0.0",
+        "foo@1.0",
+        "foo@nightly",
+    ];
+    releases.sort_by(|a, b| Release::parse(a).unwrap().cmp(&Release::parse(b).unwrap()));
+    assert_eq!(
+        releases,
+        vec![
+            "bar@1.0.0",
+            "foo@1.0.0-rc1",
+            "foo@1.0",
+            "foo@1.0.0",
+            "foo@2.0.0",
+            "085240e737828d8326719bf97730188e927e49ca",
+            "foo@a86d127c4b2f",
+            "whatever",
+            "foo@nightly",
+        ]
+    );
+}
+
+#[test]
+fn test_version_diff() {
+    let diff = |a, b| Version::parse(a).unwrap().diff(&Version::parse(b).unwrap());
+    assert_eq!(diff("1.0.0", "1.0"), VersionChange::Unchanged);
+    assert_eq!(diff("1.0.0", "2.0.0"), VersionChange::Major);
+    assert_eq!(diff("1.0.0", "2.0.0-rc1"), VersionChange::Major);
+    assert_eq!(diff("1.0.0", "1.1.0"), VersionChange::Minor);
+    assert_eq!(diff("1.0.0", "1.0.1"), VersionChange::Patch);
+    assert_eq!(diff("1.0.0", "1.0.0.1"), VersionChange::Revision);
+    assert_eq!(diff("1.0.0-rc1", "1.0.0"), VersionChange::Prerelease);
+    assert_eq!(diff("1.0.0-rc1", "1.0.0-rc2"), VersionChange::Prerelease);
+    assert_eq!(diff("1.0.0+10", "1.0.0+20"), VersionChange::Build);
+    assert_eq!(diff("1.0.0", "1.0.0+20"), VersionChange::Build);
+    assert_eq!(diff("1.0.0", "1.0.0-rc1"), VersionChange::Downgrade);
+    assert_eq!(diff("1.0.0+20", "1.0.0+10"), VersionChange::Downgrade);
+    assert_eq!(diff("2.0.0", "1.9.9"), VersionChange::Downgrade);
+    assert_eq!(diff("1.0.0+20", "1.0.0"), VersionChange::Build);
+    assert_eq!(diff("1.0.0+20", "1.0.0+abc"), VersionChange::Build);
+    assert_eq!(
+        diff(
+            "1.0+a86d127c4d6cc0d1bd3b22e3a51e3fd3ac44b1a0",
+            "1.0+0852407e737828d8326719bf97730188e927e49c"
+        ),
+        VersionChange::Build
+    );
+}
+
+#[test]
+fn test_release_diff() {
+    let diff = |a, b| Release::parse(a).unwrap().diff(&Release::parse(b).unwrap());
+
+    let change = diff("foo@1.0.0", "foo@1.1.0");
+    assert!(!change.package_changed());
+    assert_eq!(change.version_change(), Some(VersionChange::Minor));
+    assert!(!change.hash_changed());
+
+    let change = diff("foo@1.0.0", "bar@1.0.0");
+    assert!(change.package_changed());
+    assert_eq!(change.version_change(), Some(VersionChange::Unchanged));
+
+    let change = diff("foo@1.0+085240e73782", "foo@1.0+a86d127c4b2f");
+    assert_eq!(change.version_change(), Some(VersionChange::Build));
+    assert!(change.hash_changed());
+
+    let change = diff("foo@a86d127c4b2f", "foo@085240e73782");
+    assert!(!change.package_changed());
+    assert_eq!(change.version_change(), None);
+    assert!(change.hash_changed());
+
+    let change = diff("foo@a86d127c4b2f", "foo@1.0+a86d127c4b2f");
+    assert_eq!(change.version_change(), None);
+    assert!(!change.hash_changed());
+}
+
+#[test]
+fn test_owned_version() {
+    let owned: OwnedVersion = "1.0.0-rc1+42".parse().unwrap();
+    assert_eq!(owned.raw(), "1.0.0-rc1+42");
+    assert_eq!(owned.as_version().pre(), Some("rc1"));
+    assert_eq!(owned.as_version().build_number(), Some(42));
+    assert_eq!(owned, OwnedVersion::parse("1.0-rc1+42".into()).unwrap());
+    assert!(owned < OwnedVersion::parse("1.0.0".into()).unwrap());
+    assert!("1.0.0.0.0".parse::<OwnedVersion>().is_err());
+
+    // versions made from parts are kept without parsing them again
+    let raw = "train-42.3";
+    let version = Version::from_parts(raw, ("42", Some("3"), None, None), None, None).unwrap();
+    let owned = OwnedVersion::from(&version);
+    assert_eq!(owned.raw(), "train-42.3");
+    assert_eq!(owned.as_version().triple(), (42, 3, 0));
+    assert_eq!(owned.as_version(), version);
+
+    // parts that do not point into the raw version are kept as well
+    let raw = "train-42.3-rc9";
+    let version =
+        Version::from_parts(raw, ("2", Some("3"), None, None), Some(&raw[11..]), None).unwrap();
+    let owned = OwnedVersion::from(&version);
+    assert_eq!(owned.as_version().triple(), (2, 3, 0));
+    assert_eq!(owned.as_version().pre(), Some("rc9"));
+    assert_eq!(owned.as_version(), version);
+}
+
+#[test]
+fn test_columns_clamping() {
+    let release = Release::parse("foo@99999999999999999999.1+99999999999999999999").unwrap();
+    let columns = release.columns();
+    assert_eq!(columns.major(), Some(i64::MAX));
+    assert_eq!(columns.minor(), Some(1));
+    assert_eq!(columns.build_code(), Some("99999999999999999999"));
+    assert_eq!(columns.build_number(), None);
+
+    let release = Release::parse("foo@1.0+9223372036854775808").unwrap();
+    assert_eq!(release.columns().build_number(), None);
+
+    let release = Release::parse("foo@1.0+9223372036854775807").unwrap();
+    assert_eq!(release.columns().build_number(), Some(i64::MAX));
+}
+
+#[test]
+fn test_release_kind() {
+    let kind = |s| Release::parse(s).unwrap().kind();
+    assert_eq!(kind("foo@1.0.0"), ReleaseKind::Versioned);
+    assert_eq!(kind("1.0.0"), ReleaseKind::Opaque);
+    assert_eq!(
+        kind("foo@1.0.0+085240e737828d8326719bf97730188e927e49ca"),
+        ReleaseKind::VersionedWithHash
+    );
+    assert_eq!(
+        kind("085240e737828d8326719bf97730188e927e49ca"),
+        ReleaseKind::HashOnly
+    );
+    assert_eq!(kind("foo@085240e73782"), ReleaseKind::HashOnly);
+    assert_eq!(
+        kind("foo@6a8b6f3e-b7b4-4b3c-a1c2-1f2d3e4f5a6b"),
+        ReleaseKind::Uuid
+    );
+    assert_eq!(kind("1588000000"), ReleaseKind::Timestamp);
+    assert_eq!(kind("foo@1588000000000"), ReleaseKind::Timestamp);
+    assert_eq!(kind("foo@2020-05-26T01:38:42Z"), ReleaseKind::Timestamp);
+    assert_eq!(kind("some-api@2020.05.26-01.38.42"), ReleaseKind::Timestamp);
+    assert_eq!(kind("foo@2020.05.1"), ReleaseKind::CalVer);
+    assert_eq!(kind("foo@2021.12"), ReleaseKind::CalVer);
+    assert_eq!(kind("2021.12"), ReleaseKind::Opaque);
+    assert_eq!(kind("foo@2020.05.garbage"), ReleaseKind::Opaque);
+    assert_eq!(kind("foo@2020.13.1"), ReleaseKind::Versioned);
+    assert_eq!(kind("foo@bar"), ReleaseKind::Opaque);
+    assert_eq!(
+        ReleaseKind::VersionedWithHash.to_string(),
+        "versioned_with_hash"
+    );
+    assert_eq!(ReleaseKind::CalVer.to_string(), "calver");
+}
@@ -0,0 +1,85 @@
+use sentry_release_parser::{InvalidReleasePattern, Release, ReleasePattern};
+use similar_asserts::assert_eq;
+
+fn matches(pattern: &str, release: &str) -> bool {
+    ReleasePattern::parse(pattern)
+        .unwrap()
+        .matches(&Release::parse(release).unwrap())
+}
+
+#[test]
+fn test_parse() {
+    let pattern = ReleasePattern::parse(" com.acme.*@1.4.* ").unwrap();
+    assert_eq!(pattern.raw(), "com.acme.*@1.4.*");
+    assert_eq!(pattern.package(), Some("com.acme.*"));
+    assert_eq!(ReleasePattern::parse("*@a86d127c").unwrap().package(), None);
+    assert_eq!(ReleasePattern::parse("1.0").unwrap().package(), None);
+    assert_eq!(
+        ReleasePattern::parse("foo@").unwrap_err(),
+        InvalidReleasePattern
+    );
+    assert_eq!(
+        ReleasePattern::parse("").unwrap_err(),
+        InvalidReleasePattern
+    );
+}
+
+#[test]
+fn test_component_wildcards() {
+    assert!(matches("1.2.*", "foo@1.2"));
+    assert!(matches("1.2.*", "foo@1.2.0"));
+    assert!(matches("1.2.*", "foo@1.2.10-rc1"));
+    assert!(matches("1.2.*", "foo@1.2.0.5+build"));
+    assert!(!matches("1.2.*", "foo@1.20.0"));
+    assert!(!matches("1.2.*", "foo@1.3.0"));
+    assert!(matches("2.*", "foo@2"));
+    assert!(!matches("2.*", "foo@20.1"));
+    assert!(matches("com.acme.app@1.4.*", "com.acme.app@1.4.2"));
+    assert!(!matches("com.acme.app@1.4.*", "com.acme.other@1.4.2"));
+    assert!(matches("com.acme.*@2.*", "com.acme.other@2.0.0"));
+    assert!(!matches("com.acme.*@2.*", "com.other@2.0.0"));
+}
+
+#[test]
+fn test_exact_versions() {
+    assert!(matches("1.2.0", "foo@1.2"));
+    assert!(matches("1.2.0", "foo@1.2.0+1234"));
+    assert!(!matches("1.2.0", "foo@1.2.0-rc1"));
+    assert!(matches("1.2.0+1234", "foo@1.2.0+1234"));
+    assert!(!matches("1.2.0+1234", "foo@1.2.0+1235"));
+    assert!(matches("foo@*", "foo@whatever"));
+    assert!(!matches("foo@*", "bar@1.0"));
+}
+
+#[test]
+fn test_hashes() {
+    let hash = "a86d127c4d6cc0d1bd3b22e3a51e3fd3ac44b1a0";
+    assert!(matches("*@a86d127c", hash));
+    assert!(matches("a86d127c", hash));
+    assert!(matches("A86D127C", hash));
+    assert!(matches("*@a86d127c", &format!("foo@{}", hash)));
+    assert!(matches("a86d127c", &format!("foo@1.0+{}", hash)));
+    assert!(!matches("a86d127d", hash));
+    assert!(!matches("bar@a86d127c", &format!("foo@{}", hash)));
+
+    // hexadecimal words that are not build hashes match exactly
+    assert!(matches("cafe", "cafe"));
+    assert!(matches("beef", "foo@BEEF"));
+    assert!(matches("*@cafe", "app@cafe"));
+    assert!(!matches("cafe", "app@cafebabe"));
+
+    // all digit prefixes are versions and hash prefixes at the same time
+    let hash = "0852407e737828d8326719bf97730188e927e49c";
+    assert!(matches("*@0852407", &format!("foo@{}", hash)));
+    assert!(matches("0852407", &format!("foo@1.0+{}", hash)));
+    assert!(matches("*@0852407", "foo@852407"));
+    assert!(!matches("*@0852408", &format!("foo@{}", hash)));
+    assert!(!matches("*@085", &format!("foo@{}", hash)));
+}
+
+#[test]
+fn test_globs() {
+    assert!(matches("foo@*-nightly", "foo@2020-01-01-nightly"));
+    assert!(matches("*nightly*", "nightly-build"));
+    assert!(!matches("foo@*-nightly", "foo@2020-01-01"));
+}
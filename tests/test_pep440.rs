@@ -0,0 +1,142 @@
+use sentry_release_parser::{InvalidSpecifier, SpecifierSet, Version};
+use similar_asserts::assert_eq;
+
+fn matches(specifiers: &str, version: &str) -> bool {
+    SpecifierSet::parse(specifiers)
+        .unwrap()
+        .matches(&Version::parse(version).unwrap())
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        SpecifierSet::parse(">=1.0, !=1.3.*, <2")
+            .unwrap()
+            .to_string(),
+        ">=1.0,!=1.3.*,<2"
+    );
+    assert_eq!(
+        SpecifierSet::parse("~= 1.4.2").unwrap().to_string(),
+        "~=1.4.2"
+    );
+    assert_eq!(
+        SpecifierSet::parse("\t== 1.0RC1 ").unwrap().to_string(),
+        "==1.0RC1"
+    );
+    assert_eq!(SpecifierSet::parse("").unwrap().to_string(), "");
+    assert_eq!(
+        SpecifierSet::parse("===foobar").unwrap().to_string(),
+        "===foobar"
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(SpecifierSet::parse("1.0").unwrap_err(), InvalidSpecifier);
+    assert_eq!(SpecifierSet::parse("~=1").unwrap_err(), InvalidSpecifier);
+    assert_eq!(
+        SpecifierSet::parse("~=1.0.*").unwrap_err(),
+        InvalidSpecifier
+    );
+    assert_eq!(
+        SpecifierSet::parse(">=1.0.*").unwrap_err(),
+        InvalidSpecifier
+    );
+    assert_eq!(
+        SpecifierSet::parse(">=1.0+local").unwrap_err(),
+        InvalidSpecifier
+    );
+    assert_eq!(
+        SpecifierSet::parse("==1.0rc1.*").unwrap_err(),
+        InvalidSpecifier
+    );
+    assert_eq!(SpecifierSet::parse("==foo").unwrap_err(), InvalidSpecifier);
+    assert_eq!(SpecifierSet::parse(">=1.0,").unwrap().to_string(), ">=1.0");
+}
+
+#[test]
+fn test_equality() {
+    assert!(matches("==1.2", "1.2.0"));
+    assert!(matches("==1.2.0.0", "1.2"));
+    assert!(matches("==1.2", "1.2+build"));
+    assert!(!matches("==1.2+other", "1.2+build"));
+    assert!(matches("==1.2+build", "1.2+build"));
+    assert!(!matches("==1.2", "1.2.1"));
+    assert!(matches("!=1.2", "1.2.1"));
+    assert!(!matches("!=1.2", "1.2.0"));
+    assert!(matches("==1.2.*", "1.2"));
+    assert!(matches("==1.2.*", "1.2.0"));
+    assert!(matches("==1.2.*", "1.2.10"));
+    assert!(!matches("==1.2.*", "1.20.0"));
+    assert!(!matches("==1.2.*", "1.3"));
+    assert!(matches("==1.2.0.*", "1.2"));
+    assert!(!matches("!=1.3.*", "1.3.5"));
+    assert!(matches("!=1.3.*", "1.4"));
+}
+
+#[test]
+fn test_compatible() {
+    assert!(matches("~=1.4.2", "1.4.2"));
+    assert!(matches("~=1.4.2", "1.4.9"));
+    assert!(!matches("~=1.4.2", "1.4.1"));
+    assert!(!matches("~=1.4.2", "1.5.0"));
+    assert!(matches("~=1.4", "1.9"));
+    assert!(!matches("~=1.4", "2.0"));
+    assert!(matches("~=2.2.post3", "2.2.4"));
+    assert!(!matches("~=2.2.post3", "2.2"));
+}
+
+#[test]
+fn test_ordering() {
+    assert!(matches(">=1.0,!=1.3.*,<2", "1.4.2"));
+    assert!(!matches(">=1.0,!=1.3.*,<2", "1.3.1"));
+    assert!(!matches(">=1.0,!=1.3.*,<2", "2.0"));
+    assert!(!matches(">=1.0,!=1.3.*,<2", "0.9"));
+    assert!(matches("<=1.0", "1.0+build"));
+    assert!(matches(">1.0", "1.1"));
+    assert!(!matches(">1.0", "1.0-1"));
+    assert!(matches(">1.0.post1", "1.0post2"));
+    assert!(!matches(">1.0", "1.0+build"));
+    assert!(matches(">1.0", "1.0.1+build"));
+    assert!(matches("<2", "1.9"));
+    assert!(matches("<=2.0rc2", "2.0rc1"));
+    assert!(matches("<1!1.0", "5.0"));
+}
+
+#[test]
+fn test_prereleases() {
+    assert!(!matches("", "1.0rc1"));
+    assert!(matches("", "1.0"));
+    assert!(!matches(">=1.0", "2.0rc1"));
+    assert!(!matches(">=1.0", "2.0dev1"));
+    assert!(matches(">=1.0", "1.0-1"));
+    assert!(matches(">=1.0rc1", "1.0rc2"));
+    assert!(matches(">=1.0rc1", "2.0b1"));
+    assert!(!matches("<2", "2.0rc1"));
+    assert!(!matches("<2.0", "2.0dev1"));
+    assert!(matches("<=2.0rc1", "2.0b1"));
+    assert!(!matches("<2.0rc1", "2.0b1"));
+    assert!(matches("==1.0-rc.1", "1.0rc1"));
+    assert!(matches("==1.0.alpha-1", "1.0a1"));
+
+    let specifiers = SpecifierSet::parse(">=1.0").unwrap().with_prereleases(true);
+    assert!(specifiers.matches(&Version::parse("2.0rc1").unwrap()));
+    let specifiers = SpecifierSet::parse(">=1.0rc1")
+        .unwrap()
+        .with_prereleases(false);
+    assert!(!specifiers.matches(&Version::parse("1.0rc2").unwrap()));
+
+    // dev releases sort before pre-releases, post releases after the release
+    assert!(matches(">=1.0.dev1,<1.0a1", "1.0dev5"));
+    assert!(matches(">=1.0rc1,<=1.0rc2", "1.0rc1.post1"));
+    assert!(!matches(">=1.0rc1.post1,<=1.0rc2", "1.0rc1"));
+}
+
+#[test]
+fn test_arbitrary_equality() {
+    assert!(matches("===1.0", "1.0"));
+    assert!(!matches("===1.0", "1.0.0"));
+    assert!(matches("===1.0-FOO.bar", "1.0-foo.bar"));
+    assert!(!matches(">=1.0", "1.0-foo.bar"));
+    assert!(!matches("==1.0", "1.0-foo.bar"));
+}
@@ -0,0 +1,131 @@
+//! The crate depends on `regex` without its unicode features.  Dev
+//! dependencies enable them again for the tests, so patterns that need
+//! unicode tables compile here but panic for users.  This walks the syntax
+//! of the patterns instead of compiling them.
+use regex_syntax::ast::{
+    self, parse::Parser, AssertionKind, Ast, ClassSetItem, Flag, Flags, FlagsItemKind, GroupKind,
+};
+use similar_asserts::assert_eq;
+
+#[derive(Clone, Copy)]
+struct State {
+    unicode: bool,
+    case_insensitive: bool,
+}
+
+/// Finds the first construct that needs unicode tables.
+struct UnicodeTables {
+    states: Vec<State>,
+}
+
+impl UnicodeTables {
+    fn state(&self) -> State {
+        *self.states.last().unwrap()
+    }
+
+    fn apply(&mut self, flags: &Flags) {
+        let state = self.states.last_mut().unwrap();
+        let mut enable = true;
+        for item in &flags.items {
+            match item.kind {
+                FlagsItemKind::Negation => enable = false,
+                FlagsItemKind::Flag(Flag::Unicode) => state.unicode = enable,
+                FlagsItemKind::Flag(Flag::CaseInsensitive) => state.case_insensitive = enable,
+                FlagsItemKind::Flag(_) => {}
+            }
+        }
+    }
+}
+
+impl ast::Visitor for UnicodeTables {
+    type Output = ();
+    type Err = String;
+
+    fn finish(self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn visit_pre(&mut self, ast: &Ast) -> Result<(), String> {
+        let state = self.state();
+        match ast {
+            Ast::Group(group) => {
+                self.states.push(state);
+                if let GroupKind::NonCapturing(ref flags) = group.kind {
+                    self.apply(flags);
+                }
+            }
+            Ast::Flags(set) => self.apply(&set.flags),
+            Ast::ClassUnicode(_) => return Err("unicode class".into()),
+            Ast::ClassPerl(_) if state.unicode => return Err("perl class".into()),
+            Ast::Assertion(assertion) if state.unicode => match assertion.kind {
+                AssertionKind::StartLine
+                | AssertionKind::EndLine
+                | AssertionKind::StartText
+                | AssertionKind::EndText => {}
+                _ => return Err("word boundary".into()),
+            },
+            Ast::Literal(_) | Ast::ClassBracketed(_) if state.unicode && state.case_insensitive => {
+                return Err("case insensitive match".into())
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn visit_post(&mut self, ast: &Ast) -> Result<(), String> {
+        if let Ast::Group(_) = ast {
+            self.states.pop();
+        }
+        Ok(())
+    }
+
+    fn visit_class_set_item_pre(&mut self, item: &ClassSetItem) -> Result<(), String> {
+        match item {
+            ClassSetItem::Unicode(_) => Err("unicode class".into()),
+            ClassSetItem::Perl(_) if self.state().unicode => Err("perl class".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns the first construct of a pattern that needs unicode tables.
+fn needs_unicode(pattern: &str) -> Option<String> {
+    let ast = Parser::new().parse(pattern).unwrap();
+    let visitor = UnicodeTables {
+        states: vec![State {
+            unicode: true,
+            case_insensitive: false,
+        }],
+    };
+    ast::visit(&ast, visitor).err()
+}
+
+#[test]
+fn test_patterns_do_not_need_unicode() {
+    let patterns = sentry_release_parser::__regex_patterns();
+    assert!(!patterns.is_empty());
+    for pattern in patterns {
+        if let Some(construct) = needs_unicode(pattern) {
+            panic!("{} needs unicode regex features: {}", construct, pattern);
+        }
+    }
+}
+
+#[test]
+fn test_needs_unicode() {
+    assert_eq!(needs_unicode(r"^(\S+)\s+$"), Some("perl class".into()));
+    assert_eq!(needs_unicode(r"[\w.]"), Some("perl class".into()));
+    assert_eq!(needs_unicode(r"\pL"), Some("unicode class".into()));
+    assert_eq!(needs_unicode(r"a\bb"), Some("word boundary".into()));
+    assert_eq!(
+        needs_unicode(r"(?xi)^v?$"),
+        Some("case insensitive match".into())
+    );
+    assert_eq!(
+        needs_unicode(r"(?-u:(?i)a)(?i)b"),
+        Some("case insensitive match".into())
+    );
+    assert_eq!(needs_unicode(r"(?i-u)\b[a-z]+\b"), None);
+    assert_eq!(needs_unicode(r"(?-u:\b)[0-9]+(?-u:\b)"), None);
+    assert_eq!(needs_unicode(r"^[^\\/\r\n\t]*\z"), None);
+}
@@ -0,0 +1,157 @@
+use sentry_release_parser::{PackageMatcher, Parser, ParserConfig, Release, ReleaseSet, Version};
+use similar_asserts::assert_eq;
+
+fn release_set<'a>(releases: &[&'a str]) -> ReleaseSet<'a> {
+    releases
+        .iter()
+        .map(|r| Release::parse(r).unwrap())
+        .collect()
+}
+
+fn raw<'a, 'b>(releases: impl Iterator<Item = &'b Release<'a>>) -> Vec<&'a str>
+where
+    'a: 'b,
+{
+    releases.map(|r| r.raw()).collect()
+}
+
+#[test]
+fn test_insert_and_iter() {
+    let mut set = release_set(&[
+        "foo@2.0.0",
+        "bar@1.0.0",
+        "whatever",
+        "foo@1.0.0",
+        "foo@a86d127c4b2f",
+        "foo@1.0.0-rc1",
+    ]);
+    assert_eq!(set.len(), 6);
+    assert!(!set.insert(Release::parse("foo@1.0.0").unwrap()));
+    assert!(set.insert(Release::parse("foo@1.0").unwrap()));
+    assert_eq!(set.len(), 7);
+    assert!(set.contains(&Release::parse("foo@1.0").unwrap()));
+    assert!(set.contains(&Release::parse("whatever").unwrap()));
+    assert!(!set.contains(&Release::parse("foo@3.0").unwrap()));
+
+    assert_eq!(set.packages().collect::<Vec<_>>(), vec!["bar", "foo"]);
+    assert_eq!(
+        raw(set.iter()),
+        vec![
+            "bar@1.0.0",
+            "foo@1.0.0-rc1",
+            "foo@1.0",
+            "foo@1.0.0",
+            "foo@2.0.0",
+            "foo@a86d127c4b2f",
+            "whatever",
+        ]
+    );
+    assert_eq!(
+        raw(set.unversioned().iter()),
+        vec!["foo@a86d127c4b2f", "whatever"]
+    );
+    assert!(set.releases("baz").is_empty());
+}
+
+#[test]
+fn test_latest() {
+    let set = release_set(&["foo@1.0.0", "foo@2.0.0-rc1", "foo@1.5.0", "bar@3.0"]);
+    assert_eq!(set.latest("foo", false).unwrap().raw(), "foo@1.5.0");
+    assert_eq!(set.latest("foo", true).unwrap().raw(), "foo@2.0.0-rc1");
+    assert_eq!(set.latest("bar", false).unwrap().raw(), "bar@3.0");
+    assert_eq!(set.latest("baz", true), None);
+
+    let set = release_set(&["foo@1.0.0-rc1"]);
+    assert_eq!(set.latest("foo", false), None);
+}
+
+#[test]
+fn test_range() {
+    let set = release_set(&[
+        "foo@1.0.0",
+        "foo@2.0.0",
+        "foo@2.1.0",
+        "foo@2.5.3",
+        "foo@3.0.0-rc1",
+        "foo@3.0.0",
+        "bar@2.2.0",
+    ]);
+    let v = |s| Version::parse(s).unwrap();
+
+    assert_eq!(
+        raw(set.range("foo", v("2.0")..v("3.0"))),
+        vec!["foo@2.0.0", "foo@2.1.0", "foo@2.5.3", "foo@3.0.0-rc1"]
+    );
+    assert_eq!(
+        raw(set.range("foo", v("2.0")..=v("3.0"))),
+        vec![
+            "foo@2.0.0",
+            "foo@2.1.0",
+            "foo@2.5.3",
+            "foo@3.0.0-rc1",
+            "foo@3.0.0"
+        ]
+    );
+    assert_eq!(raw(set.range("foo", ..v("2.0"))), vec!["foo@1.0.0"]);
+    assert_eq!(
+        raw(set.range("foo", v("2.5.3")..)),
+        vec!["foo@2.5.3", "foo@3.0.0-rc1", "foo@3.0.0"]
+    );
+    assert_eq!(
+        raw(set.range("foo", v("3.0")..v("2.0"))),
+        Vec::<&str>::new()
+    );
+    assert_eq!(raw(set.range("bar", ..)), vec!["bar@2.2.0"]);
+}
+
+#[test]
+fn test_previous_next() {
+    let set = release_set(&["foo@1.0.0", "foo@2.0.0", "foo@3.0.0", "bar@2.5.0"]);
+    let release = |s| Release::parse(s).unwrap();
+
+    assert_eq!(
+        set.previous(&release("foo@2.0.0")).unwrap().raw(),
+        "foo@1.0.0"
+    );
+    assert_eq!(set.next(&release("foo@2.0.0")).unwrap().raw(), "foo@3.0.0");
+    assert_eq!(
+        set.previous(&release("foo@2.5.0")).unwrap().raw(),
+        "foo@2.0.0"
+    );
+    assert_eq!(set.next(&release("foo@2.5.0")).unwrap().raw(), "foo@3.0.0");
+    assert_eq!(set.previous(&release("foo@1.0.0")), None);
+    assert_eq!(set.next(&release("foo@3.0.0")), None);
+    assert_eq!(set.next(&release("foo@a86d127c4b2f")), None);
+    assert_eq!(set.previous(&release("baz@1.0.0")), None);
+}
+
+#[test]
+fn test_schemes() {
+    let config = ParserConfig::new().with_rule(PackageMatcher::Exact("api".into()), "pep440");
+    let parser = Parser::from_config(&config).unwrap();
+    let set: ReleaseSet = ["api@1.0.post9", "api@1.0.post10", "api@1.0", "api@1.1.dev1"]
+        .iter()
+        .map(|r| Release::parse_with(r, &parser).unwrap())
+        .collect();
+
+    assert_eq!(set.latest("api", false).unwrap().raw(), "api@1.0.post10");
+    assert_eq!(set.latest("api", true).unwrap().raw(), "api@1.1.dev1");
+
+    let post9 = Release::parse_with("api@1.0.post9", &parser).unwrap();
+    assert_eq!(set.next(&post9).unwrap().raw(), "api@1.0.post10");
+    assert_eq!(set.previous(&post9).unwrap().raw(), "api@1.0");
+
+    let lower = Version::parse("1.0").unwrap();
+    let upper = Version::parse("1.1").unwrap();
+    assert_eq!(
+        raw(set.range("api", lower..upper)),
+        ["api@1.0", "api@1.0.post9", "api@1.0.post10", "api@1.1.dev1"]
+    );
+
+    // releases of other schemes are grouped separately
+    let mut set = set;
+    set.insert(Release::parse("api@1.0.5").unwrap());
+    assert_eq!(set.releases("api").first().unwrap().raw(), "api@1.0.5");
+    assert_eq!(set.next(&post9).unwrap().raw(), "api@1.0.post10");
+    assert_eq!(set.latest("api", false).unwrap().raw(), "api@1.0.5");
+}
@@ -0,0 +1,139 @@
+use sentry_release_parser::{
+    sanitize_environment, sanitize_environment_with, sanitize_release, sanitize_release_with,
+    validate_release, validate_release_with, CharacterPolicy, LengthUnit, Release, SanitizeChange,
+    ValidationConfig,
+};
+use similar_asserts::assert_eq;
+
+#[test]
+fn test_valid_unchanged() {
+    let sanitized = sanitize_release("org.example.FooApp@1.0rc1+20200101100");
+    assert_eq!(sanitized.value(), "org.example.FooApp@1.0rc1+20200101100");
+    assert!(!sanitized.is_changed());
+    assert!(!sanitize_environment("production").is_changed());
+}
+
+#[test]
+fn test_characters() {
+    let sanitized = sanitize_release(" foo/bar@1.0\tbeta\\2\x00\n");
+    assert_eq!(sanitized.value(), "foo-bar@1.0 beta-2");
+    assert_eq!(
+        sanitized.changes(),
+        [
+            SanitizeChange::Trimmed,
+            SanitizeChange::ReplacedCharacter {
+                character: '/',
+                replacement: '-',
+                offset: 4
+            },
+            SanitizeChange::ReplacedCharacter {
+                character: '\t',
+                replacement: ' ',
+                offset: 12
+            },
+            SanitizeChange::ReplacedCharacter {
+                character: '\\',
+                replacement: '-',
+                offset: 17
+            },
+            SanitizeChange::RemovedCharacter {
+                character: '\x00',
+                offset: 19
+            },
+        ]
+    );
+    assert_eq!(
+        sanitized.changes()[1].to_string(),
+        "replaced '/' at position 4 with '-'"
+    );
+
+    let sanitized = sanitize_environment("\x01 prod");
+    assert_eq!(sanitized.value(), "prod");
+    assert_eq!(sanitized.changes()[0], SanitizeChange::Trimmed);
+
+    let config = ValidationConfig::release().with_character_policy(CharacterPolicy::PrintableAscii);
+    assert_eq!(
+        sanitize_release_with("foo@1.0-ßeta", &config).value(),
+        "foo@1.0-eta"
+    );
+}
+
+#[test]
+fn test_restricted() {
+    let sanitized = sanitize_release("Latest");
+    assert_eq!(sanitized.value(), "_Latest");
+    assert_eq!(
+        sanitized.changes(),
+        [SanitizeChange::RenamedRestricted {
+            name: "Latest".into()
+        }]
+    );
+    assert_eq!(sanitize_environment("..").value(), "_..");
+
+    // names are checked after truncation
+    let config = ValidationConfig::release().with_max_length(6);
+    let sanitized = sanitize_release_with("latestabc", &config);
+    assert_eq!(sanitized.value(), "_lates");
+    assert_eq!(
+        sanitized.changes(),
+        [
+            SanitizeChange::Truncated {
+                length: 9,
+                limit: 6
+            },
+            SanitizeChange::RenamedRestricted {
+                name: "latest".into()
+            },
+        ]
+    );
+    assert_eq!(validate_release_with(sanitized.value(), &config), Ok(()));
+
+    let sanitized = sanitize_release_with("Latest", &config);
+    assert_eq!(sanitized.value(), "_Lates");
+    assert_eq!(validate_release_with(sanitized.value(), &config), Ok(()));
+}
+
+#[test]
+fn test_truncate_release() {
+    let hash = "a86d127c4b2f97e4ef5ef7c0ba0d4d4b2e4ac1e5";
+
+    // the package is shortened first
+    let release = format!("{}@1.0+{}", "p".repeat(200), hash);
+    let sanitized = sanitize_release(&release);
+    assert_eq!(sanitized.value().len(), 200);
+    assert!(sanitized.value().ends_with(&format!("@1.0+{}", hash)));
+    assert_eq!(
+        sanitized.changes(),
+        [SanitizeChange::Truncated {
+            length: 245,
+            limit: 200
+        }]
+    );
+
+    // long versions keep the package and the build hash
+    let release = format!("foo@1.0-{}+{}", "x".repeat(200), hash);
+    let sanitized = sanitize_release(&release);
+    assert_eq!(sanitized.value().len(), 200);
+    assert!(sanitized.value().starts_with("foo@1.0-xxx"));
+    assert!(sanitized.value().ends_with(&format!("+{}", hash)));
+    let parsed = Release::parse(sanitized.value()).unwrap();
+    assert_eq!(parsed.package(), Some("foo"));
+    assert_eq!(parsed.build_hash(), Some(hash));
+
+    // without a package the release is cut at the end
+    let sanitized = sanitize_release(&"x".repeat(250));
+    assert_eq!(sanitized.value(), "x".repeat(200));
+}
+
+#[test]
+fn test_truncate_char_boundary() {
+    let release = format!("foo@1.0-{}", "ä".repeat(100));
+    let sanitized = sanitize_release(&release);
+    assert_eq!(sanitized.value().len(), 200);
+    assert_eq!(validate_release(sanitized.value()), Ok(()));
+
+    let config = ValidationConfig::environment()
+        .with_max_length(3)
+        .with_length_unit(LengthUnit::Chars);
+    assert_eq!(sanitize_environment_with("ääää", &config).value(), "äää");
+}
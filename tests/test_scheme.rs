@@ -0,0 +1,173 @@
+use std::cmp::Ordering;
+
+use sentry_release_parser::{Parser, Release, Version, VersionScheme};
+use similar_asserts::assert_eq;
+
+/// Parses `train-<train>.<build>` versions.
+struct TrainScheme;
+
+impl VersionScheme for TrainScheme {
+    fn name(&self) -> &'static str {
+        "train"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        let rest = version.strip_prefix("train-")?;
+        let (train, build) = rest.split_once('.')?;
+        Version::from_parts(version, (train, Some(build), None, None), None, None).ok()
+    }
+
+    fn describe(&self, version: &Version<'_>) -> String {
+        format!("train {} (build {})", version.major(), version.minor())
+    }
+}
+
+/// Parses firmware versions like `FW0102` as `1.2` with reversed ordering.
+struct ReversedFirmwareScheme;
+
+impl VersionScheme for ReversedFirmwareScheme {
+    fn name(&self) -> &'static str {
+        "firmware"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        let digits = version.strip_prefix("FW")?;
+        if digits.len() != 4 {
+            return None;
+        }
+        Version::from_parts(
+            version,
+            (&digits[..2], Some(&digits[2..]), None, None),
+            None,
+            None,
+        )
+        .ok()
+    }
+
+    fn compare(&self, a: &Version<'_>, b: &Version<'_>) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Parses the same versions as the default scheme under a different name.
+struct PlainScheme;
+
+impl VersionScheme for PlainScheme {
+    fn name(&self) -> &'static str {
+        "plain"
+    }
+
+    fn parse<'a>(&self, version: &'a str) -> Option<Version<'a>> {
+        Version::parse(version).ok()
+    }
+}
+
+fn parser() -> Parser {
+    let mut parser = Parser::new();
+    parser
+        .register(TrainScheme)
+        .register(ReversedFirmwareScheme);
+    parser
+}
+
+#[test]
+fn test_default_scheme() {
+    let release = Release::parse("foo@1.0.0").unwrap();
+    assert_eq!(release.scheme(), Some("default"));
+    assert_eq!(Release::parse("foo@bar").unwrap().scheme(), None);
+    assert_eq!(Release::parse("1.0.0").unwrap().scheme(), None);
+
+    let parser = Parser::new();
+    assert_eq!(
+        parser.schemes().map(|s| s.name()).collect::<Vec<_>>(),
+        ["default"]
+    );
+    assert_eq!(Release::parse_with("foo@1.0.0", &parser).unwrap(), release);
+    assert_eq!(
+        parser
+            .describe(&Release::parse("foo@1.0+085240e737828d8326719bf97730188e927e49ca").unwrap()),
+        "1.0 (085240e73782)"
+    );
+}
+
+#[test]
+fn test_custom_schemes() {
+    let parser = parser();
+    assert_eq!(
+        parser.schemes().map(|s| s.name()).collect::<Vec<_>>(),
+        ["train", "firmware", "default"]
+    );
+
+    let release = Release::parse_with("app@train-42.3", &parser).unwrap();
+    assert_eq!(release.scheme(), Some("train"));
+    assert_eq!(release.version().unwrap().quad(), (42, 3, 0, 0));
+    assert_eq!(release.version().unwrap().components(), 2);
+    assert_eq!(parser.describe(&release), "train 42 (build 3)");
+    assert_eq!(Release::parse("app@train-42.3").unwrap().version(), None);
+
+    let release = parser.parse("app@1.2.3").unwrap();
+    assert_eq!(release.scheme(), Some("default"));
+    assert_eq!(parser.describe(&release), "1.2.3");
+
+    let release = parser.parse("app@train-x").unwrap();
+    assert_eq!(release.scheme(), None);
+    assert_eq!(parser.describe(&release), "app@train-x");
+}
+
+#[test]
+fn test_compare() {
+    let parser = parser();
+    let compare = |a, b| parser.compare(&parser.parse(a).unwrap(), &parser.parse(b).unwrap());
+    assert_eq!(
+        compare("app@train-42.3", "app@train-42.10"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(compare("fw@FW0102", "fw@FW0103"), Some(Ordering::Greater));
+    assert_eq!(compare("fw@FW0102", "fw@1.2"), None);
+    assert_eq!(compare("a@1.0", "b@1.0"), None);
+    assert_eq!(compare("a@1.0", "a@2.0"), Some(Ordering::Less));
+}
+
+#[test]
+fn test_ordering_is_consistent_with_equality() {
+    let mut plain = Parser::new();
+    plain.register(PlainScheme);
+    let a = Release::parse("app@1.2.3").unwrap();
+    let b = Release::parse_with("app@1.2.3", &plain).unwrap();
+    assert_eq!(b.scheme(), Some("plain"));
+    assert!(a != b);
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(
+        a.cmp(&Release::parse("app@1.2.3").unwrap()),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn test_from_parts() {
+    let raw = "1.2-rc1+77";
+    let version = Version::from_parts(
+        raw,
+        ("1", Some("2"), None, None),
+        Some(&raw[4..7]),
+        Some(&raw[8..]),
+    )
+    .unwrap();
+    assert_eq!(version.raw_short(), "1.2-rc1");
+    assert_eq!(version.pre(), Some("rc1"));
+    assert_eq!(version.build_number(), Some(77));
+    assert_eq!(version, Version::parse("1.2-rc1+77").unwrap());
+
+    assert!(Version::from_parts("x", ("x", None, None, None), None, None).is_err());
+    assert!(Version::from_parts("1", ("1", None, Some("2"), None), None, None).is_err());
+    assert!(Version::from_parts("1.", ("1", Some(""), None, None), None, None).is_err());
+
+    // pre-releases and build codes are checked like in `Version::parse`
+    let raw = "1-rc\0x+a+b";
+    let parts = ("1", None, None, None);
+    assert!(Version::from_parts(raw, parts, Some(&raw[2..6]), None).is_err());
+    assert!(Version::from_parts(raw, parts, None, Some(&raw[7..])).is_err());
+    assert!(Version::from_parts(raw, parts, Some(&raw[2..4]), Some(&raw[9..])).is_ok());
+    assert!(Version::from_parts(raw, parts, Some("rc"), None).is_err());
+    assert!(Version::from_parts(raw, parts, None, Some("b")).is_err());
+}
@@ -0,0 +1,139 @@
+use sentry_release_parser::{
+    InvalidSearchQuery, Release, ReleaseStage, SearchKey, SearchOperator, SearchQuery,
+};
+use similar_asserts::assert_eq;
+
+fn matches(query: &str, release: &str) -> bool {
+    SearchQuery::parse(query)
+        .unwrap()
+        .matches(&Release::parse(release).unwrap())
+}
+
+#[test]
+fn test_parse() {
+    let query =
+        SearchQuery::parse(r#"is:unresolved !release:[a, "b c"] release.build:>100 some text"#)
+            .unwrap();
+    assert_eq!(query.filters().len(), 2);
+
+    let filter = &query.filters()[0];
+    assert_eq!(filter.key(), SearchKey::Release);
+    assert!(filter.is_negated());
+    assert_eq!(filter.operator(), SearchOperator::Equal);
+    assert_eq!(filter.values(), ["a", "b c"]);
+
+    let filter = &query.filters()[1];
+    assert_eq!(filter.key(), SearchKey::Build);
+    assert!(!filter.is_negated());
+    assert_eq!(filter.operator(), SearchOperator::Greater);
+    assert_eq!(filter.values(), ["100"]);
+
+    assert_eq!(
+        query.to_string(),
+        r#"!release:[a, "b c"] release.build:>100"#
+    );
+    assert_eq!(
+        SearchQuery::parse(r#"release:"a \"quoted\" name""#)
+            .unwrap()
+            .filters()[0]
+            .values(),
+        [r#"a "quoted" name"#]
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(SearchQuery::parse("release:"), Err(InvalidSearchQuery));
+    assert_eq!(SearchQuery::parse("release:\"foo"), Err(InvalidSearchQuery));
+    assert_eq!(SearchQuery::parse("release:[a,b"), Err(InvalidSearchQuery));
+    assert_eq!(SearchQuery::parse("release:>foo"), Err(InvalidSearchQuery));
+    assert_eq!(
+        SearchQuery::parse("release.version:>[1,2]"),
+        Err(InvalidSearchQuery)
+    );
+    assert_eq!(
+        SearchQuery::parse("release.version:>=1.*"),
+        Err(InvalidSearchQuery)
+    );
+    assert_eq!(
+        SearchQuery::parse("release.version:abc"),
+        Err(InvalidSearchQuery)
+    );
+    assert_eq!(
+        SearchQuery::parse("release.build:>abc"),
+        Err(InvalidSearchQuery)
+    );
+    assert_eq!(
+        SearchQuery::parse("release.stage:gone"),
+        Err(InvalidSearchQuery)
+    );
+    assert_eq!(
+        SearchQuery::parse("release:a OR release:b"),
+        Err(InvalidSearchQuery)
+    );
+}
+
+#[test]
+fn test_release() {
+    assert!(matches("release:foo@1.0", "foo@1.0"));
+    assert!(!matches("release:foo@1.0", "foo@1.0.0"));
+    assert!(matches("release:foo@*", "foo@1.0.0"));
+    assert!(matches("release:*a86d127c*", "a86d127c4d6c"));
+    assert!(matches("release:[foo@1.0,foo@2.0]", "foo@2.0"));
+    assert!(!matches("!release:[foo@1.0,foo@2.0]", "foo@2.0"));
+    assert!(matches("!release:[foo@1.0,foo@2.0]", "foo@3.0"));
+    assert!(matches("", "foo@3.0"));
+
+    // quoted values can span lines but are never dropped
+    let query = SearchQuery::parse("release:\"foo\nbar\"").unwrap();
+    assert_eq!(query.filters().len(), 1);
+    assert!(!query.matches(&Release::parse("foo").unwrap()));
+}
+
+#[test]
+fn test_version() {
+    assert!(matches("release.version:>=1.2.0", "foo@1.2.0"));
+    assert!(matches("release.version:>=1.2.0", "foo@1.10"));
+    assert!(!matches("release.version:>=1.2.0", "foo@1.2.0-rc1"));
+    assert!(!matches("release.version:>=1.2.0", "a86d127c4d6c"));
+    assert!(matches("release.version:<1.2.0", "foo@1.2.0-rc1"));
+    assert!(matches("release.version:1.2.0", "foo@1.2"));
+    assert!(matches("release.version:>foo@1.0", "foo@1.1"));
+    assert!(!matches("release.version:>foo@1.0", "bar@1.1"));
+    assert!(matches("release.version:@scope-bar@1.0", "@scope-bar@1.0"));
+    assert!(matches("release.version:1.2.*", "foo@1.2"));
+    assert!(matches("release.version:1.2.*", "foo@1.2.10-rc1"));
+    assert!(!matches("release.version:1.2.*", "foo@1.20.0"));
+    assert!(matches("release.version:foo@*", "foo@1.20.0"));
+    assert!(!matches("release.version:foo@*", "bar@1.20.0"));
+    assert!(matches("!release.version:1.*", "foo@2.0"));
+}
+
+#[test]
+fn test_package() {
+    assert!(matches("release.package:com.acme.*", "com.acme.app@1.0"));
+    assert!(!matches("release.package:com.acme.*", "com.other.app@1.0"));
+    assert!(!matches("release.package:com.acme.*", "1.0"));
+    assert!(matches("!release.package:com.acme.*", "1.0"));
+}
+
+#[test]
+fn test_build() {
+    assert!(matches("release.build:>100", "foo@1.0+101"));
+    assert!(!matches("release.build:>100", "foo@1.0+100"));
+    assert!(!matches("release.build:>100", "foo@1.0+abc"));
+    assert!(matches("release.build:100", "foo@1.0+100"));
+    assert!(matches("release.build:abc*", "foo@1.0+abcdef"));
+    assert!(matches("release.build:abcdef", "foo@1.0+abcdef"));
+    assert!(!matches("release.build:abc", "foo@1.0+abcdef"));
+}
+
+#[test]
+fn test_stage() {
+    let query = SearchQuery::parse("release.stage:[adopted,low_adoption]").unwrap();
+    let release = Release::parse("foo@1.0").unwrap();
+    assert!(!query.matches(&release));
+    assert!(query.matches_with_stage(&release, Some(ReleaseStage::Adopted)));
+    assert!(query.matches_with_stage(&release, Some(ReleaseStage::LowAdoption)));
+    assert!(!query.matches_with_stage(&release, Some(ReleaseStage::Replaced)));
+}
@@ -1,5 +1,5 @@
 #![cfg(feature = "semver")]
-use sentry_release_parser::Release;
+use sentry_release_parser::{OwnedVersion, Release};
 use similar_asserts::assert_eq;
 
 #[test]
@@ -22,3 +22,20 @@ fn test_basic() {
         }
     )
 }
+
+#[test]
+fn test_from_semver() {
+    let semver = semver::Version::parse("1.2.3-alpha.1+build.5").unwrap();
+    let owned = OwnedVersion::from(&semver);
+    assert_eq!(owned.to_string(), "1.2.3-alpha.1+build.5");
+    assert_eq!(owned.as_version().as_semver(), semver);
+
+    let semver = semver::Version {
+        major: 1,
+        minor: 2,
+        patch: 3,
+        pre: vec![semver::Identifier::AlphaNumeric("dev".into())],
+        build: vec![semver::Identifier::AlphaNumeric("not valid".into())],
+    };
+    assert_eq!(OwnedVersion::from(&semver).to_string(), "1.2.3-dev");
+}
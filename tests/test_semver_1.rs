@@ -1,6 +1,8 @@
 #![cfg(feature = "semver-1")]
 
-use sentry_release_parser::Version;
+use std::convert::TryFrom;
+
+use sentry_release_parser::{OwnedVersion, Version};
 use similar_asserts::assert_eq;
 
 #[test]
@@ -21,3 +23,81 @@ fn test_basic() {
     assert_eq!(semver2.pre, semver_1::Prerelease::EMPTY);
     assert_eq!(semver2.build, semver_1::BuildMetadata::EMPTY);
 }
+
+#[test]
+fn test_try_from() {
+    let version = Version::parse("1.2.3-dev+BUILD-code").unwrap();
+    let semver = semver_1::Version::try_from(&version).unwrap();
+    assert_eq!(semver.to_string(), "1.2.3-dev+BUILD-code");
+
+    let version = Version::parse("1.2.3.0").unwrap();
+    assert!(semver_1::Version::try_from(&version).is_ok());
+
+    let version = Version::parse("1.2.3.4").unwrap();
+    let err = semver_1::Version::try_from(&version).unwrap_err();
+    assert!(err.conversion().loses_revision());
+    assert!(!err.conversion().loses_pre());
+    assert!(!err.conversion().loses_build_code());
+    assert_eq!(err.to_string(), "lossy semver conversion: loses revision");
+
+    let version = Version::parse("1.2.3.4-.a").unwrap();
+    let conversion = version.semver1_conversion();
+    assert!(!conversion.is_lossless());
+    assert!(conversion.loses_revision());
+    assert!(conversion.loses_pre());
+    assert_eq!(conversion.to_string(), "loses revision, pre-release");
+    assert_eq!(version.as_semver1().pre, semver_1::Prerelease::EMPTY);
+}
+
+#[test]
+fn test_from_semver() {
+    let semver = semver_1::Version::parse("1.2.3-alpha.1+build.5").unwrap();
+    let owned = OwnedVersion::from(&semver);
+    let version = owned.as_version();
+    assert_eq!(version.triple(), (1, 2, 3));
+    assert_eq!(version.pre(), Some("alpha.1"));
+    assert_eq!(version.build_code(), Some("build.5"));
+    assert_eq!(semver_1::Version::try_from(&version).unwrap(), semver);
+
+    let version = Version::parse("1.0rc1+20200101100").unwrap();
+    let semver = semver_1::Version::try_from(&version).unwrap();
+    assert_eq!(OwnedVersion::from(&semver).as_version(), version);
+}
+
+#[test]
+fn test_matches_semver_req() {
+    let req = |s| semver_1::VersionReq::parse(s).unwrap();
+    let ver = |s| Version::parse(s).unwrap();
+
+    assert_eq!(ver("1.4.0").matches_semver_req(&req(">=1.2.0")), Ok(true));
+    assert_eq!(ver("1.1").matches_semver_req(&req(">=1.2.0")), Ok(false));
+    assert_eq!(ver("1.2.0.0").matches_semver_req(&req("=1.2.0")), Ok(true));
+    assert_eq!(
+        ver("1.2.0+BUILD").matches_semver_req(&req("^1.2")),
+        Ok(true)
+    );
+
+    // implied pre-releases follow the semver pre-release rules
+    assert_eq!(
+        ver("1.3.0rc1").matches_semver_req(&req(">=1.2.0")),
+        Ok(false)
+    );
+    assert_eq!(
+        ver("1.3.0rc1").matches_semver_req(&req(">=1.3.0-alpha")),
+        Ok(true)
+    );
+    assert_eq!(
+        ver("1.0rc1").matches_semver_req(&req("=1.0.0-rc1")),
+        Ok(true)
+    );
+
+    let err = ver("1.2.0.1")
+        .matches_semver_req(&req(">=1.2.0"))
+        .unwrap_err();
+    assert!(err.conversion().loses_revision());
+
+    let err = ver("1.2.0-.a")
+        .matches_semver_req(&req(">=1.2.0"))
+        .unwrap_err();
+    assert!(err.conversion().loses_pre());
+}
@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+
+use proptest::prelude::*;
+use sentry_release_parser::{InvalidSortKey, SortKey, Version};
+use similar_asserts::assert_eq;
+
+const VERSION_PATTERN: &str = r"[0-9]{1,3}(\.[0-9]{1,3}){0,3}(-(rc|alpha|beta|dev)[0-9]{0,2})?(\+([0-9]{1,4}|[a-zA-Z0-9]{1,4}))?";
+
+fn build_class(version: &Version) -> u8 {
+    match (version.build_code(), version.build_number()) {
+        (None, _) => 0,
+        (Some(_), Some(_)) => 1,
+        (Some(_), None) => 2,
+    }
+}
+
+#[test]
+fn test_basic_ordering() {
+    let mut versions = vec![
+        "1.0.0+abc",
+        "2.0",
+        "1.0.0-rc1",
+        "1.0.0+20",
+        "0.9",
+        "1.0.0",
+        "1.0.0+10",
+        "1.0.0-alpha",
+        "1.0.0.1",
+    ];
+    versions.sort_by_key(|v| Version::parse(v).unwrap().sort_key());
+    assert_eq!(
+        versions,
+        vec![
+            "0.9",
+            "1.0.0-alpha",
+            "1.0.0-rc1",
+            "1.0.0",
+            "1.0.0+10",
+            "1.0.0+20",
+            "1.0.0+abc",
+            "1.0.0.1",
+            "2.0",
+        ]
+    );
+}
+
+#[test]
+fn test_decode() {
+    let version = Version::parse("1.2.3.4-rc1+1234").unwrap();
+    let key = SortKey::new(&version);
+    assert_eq!(key.quad(), (1, 2, 3, 4));
+    assert_eq!(key.pre(), Some("rc1"));
+    assert_eq!(key.build_code(), Some("1234"));
+    assert_eq!(key.to_string(), "1.2.3.4-rc1+1234");
+
+    let key = SortKey::from_bytes(key.into_bytes()).unwrap();
+    assert_eq!(key.to_string(), "1.2.3.4-rc1+1234");
+
+    let version = Version::parse("1.0").unwrap();
+    assert_eq!(SortKey::new(&version).to_string(), "1.0.0.0");
+
+    let raw = "train-42.3-rc9";
+    let version =
+        Version::from_parts(raw, ("42", Some("3"), None, None), Some(&raw[11..]), None).unwrap();
+    let key = SortKey::new(&version);
+    assert_eq!(key.pre(), Some("rc9"));
+    assert_eq!(key.to_string(), "42.3.0.0-rc9");
+}
+
+#[test]
+fn test_invalid_bytes() {
+    assert_eq!(SortKey::from_bytes(vec![]), Err(InvalidSortKey));
+    assert_eq!(SortKey::from_bytes(vec![0; 33]), Err(InvalidSortKey));
+
+    let mut bytes = Version::parse("1.0+42").unwrap().sort_key();
+    bytes.push(b'x');
+    assert_eq!(SortKey::from_bytes(bytes), Err(InvalidSortKey));
+}
+
+proptest! {
+    #[test]
+    fn test_order_matches_cmp(a in VERSION_PATTERN, b in VERSION_PATTERN) {
+        let a = Version::parse(&a).unwrap();
+        let b = Version::parse(&b).unwrap();
+        let key_order = a.sort_key().cmp(&b.sort_key());
+        let same_precedence =
+            a.quad() == b.quad() && a.pre() == b.pre();
+
+        if !same_precedence || build_class(&a) == build_class(&b) || build_class(&a) == 0 || build_class(&b) == 0 {
+            match a.cmp(&b) {
+                Ordering::Equal => prop_assert_eq!(key_order, a.build_code().cmp(&b.build_code())),
+                ordering => prop_assert_eq!(key_order, ordering),
+            }
+        } else {
+            prop_assert_eq!(key_order, build_class(&a).cmp(&build_class(&b)));
+        }
+    }
+
+    #[test]
+    fn test_roundtrip(raw in VERSION_PATTERN) {
+        let version = Version::parse(&raw).unwrap();
+        let key = SortKey::from_bytes(version.sort_key()).unwrap();
+        let formatted = key.to_string();
+        let decoded = Version::parse(&formatted).unwrap();
+        prop_assert_eq!(&decoded, &version);
+        prop_assert_eq!(decoded.cmp(&version), Ordering::Equal);
+        prop_assert_eq!(decoded.sort_key(), version.sort_key());
+    }
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "unicode")]
+use sentry_release_parser::{
+    normalize_nfc, sanitize_release_with, validate_environment_with, validate_release,
+    validate_release_with, InvalidEnvironment, InvalidRelease, Parser, Release, SanitizeChange,
+    UnicodeIssue, UnicodePolicy, ValidationConfig,
+};
+use similar_asserts::assert_eq;
+
+fn config() -> ValidationConfig {
+    ValidationConfig::release().with_unicode_policy(UnicodePolicy::new())
+}
+
+#[test]
+fn test_invisible_characters() {
+    assert_eq!(validate_release("my\u{200b}app@1.0"), Ok(()));
+    assert_eq!(
+        validate_release_with("my\u{200b}app@1.0", &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::ZeroWidth {
+            character: '\u{200b}',
+            offset: 2
+        }))
+    );
+    assert_eq!(
+        validate_release_with("myapp@1.0\u{202e}", &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::BidiControl {
+            character: '\u{202e}',
+            offset: 9
+        }))
+    );
+    assert_eq!(
+        validate_environment_with(
+            "prod\u{feff}",
+            &ValidationConfig::environment().with_unicode_policy(UnicodePolicy::new())
+        ),
+        Err(InvalidEnvironment::UnsafeUnicode(UnicodeIssue::ZeroWidth {
+            character: '\u{feff}',
+            offset: 4
+        }))
+    );
+
+    let policy = UnicodePolicy::new().with_zero_width_check(false);
+    let config = ValidationConfig::release().with_unicode_policy(policy);
+    assert_eq!(validate_release_with("my\u{200b}app@1.0", &config), Ok(()));
+}
+
+#[test]
+fn test_normalization() {
+    let nfd = "cafe\u{301}@1.0";
+    assert_eq!(normalize_nfc(nfd), "caf\u{e9}@1.0");
+    assert_eq!(normalize_nfc("caf\u{e9}@1.0"), "caf\u{e9}@1.0");
+    assert_eq!(
+        validate_release_with(nfd, &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::NotNormalized))
+    );
+    assert_eq!(validate_release_with("caf\u{e9}@1.0", &config()), Ok(()));
+}
+
+#[test]
+fn test_mixed_script() {
+    // the second letter is a cyrillic a
+    let err = validate_release_with("m\u{430}pp@1.0", &config()).unwrap_err();
+    assert_eq!(
+        err,
+        InvalidRelease::UnsafeUnicode(UnicodeIssue::MixedScript {
+            first: "Latin",
+            second: "Cyrillic"
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid release: unsafe unicode in release name"
+    );
+    assert_eq!(
+        format!("{:#}", err),
+        "invalid release: unsafe unicode in release name (package mixes Latin and Cyrillic script)"
+    );
+
+    // only the package is checked and digits and punctuation are common
+    assert_eq!(
+        validate_release_with("\u{43f}\u{430}-2@1.0-m\u{430}", &config()),
+        Ok(())
+    );
+    assert_eq!(validate_release_with("m\u{430}pp", &config()), Ok(()));
+
+    // Japanese, Chinese and Korean scripts can be mixed with latin
+    for release in [
+        "日本のアプリ@1.0",
+        "漢字app@1.0",
+        "注音ㄅㄆ-app@1.0",
+        "한국어app@1.0",
+    ] {
+        assert_eq!(validate_release_with(release, &config()), Ok(()));
+    }
+    assert_eq!(
+        validate_release_with("한국カナ@1.0", &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::MixedScript {
+            first: "Hangul",
+            second: "Katakana"
+        }))
+    );
+
+    // the reported scripts are the ones that cannot be mixed
+    assert_eq!(
+        validate_release_with("abc漢字Кир@1.0", &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::MixedScript {
+            first: "Latin",
+            second: "Cyrillic"
+        }))
+    );
+    assert_eq!(
+        validate_release_with("漢字abcカナ한국@1.0", &config()),
+        Err(InvalidRelease::UnsafeUnicode(UnicodeIssue::MixedScript {
+            first: "Katakana",
+            second: "Hangul"
+        }))
+    );
+}
+
+#[test]
+fn test_parser_and_sanitize() {
+    let mut parser = Parser::new();
+    parser.set_validation(config());
+    assert!(Release::parse_with("myapp@1.0", &parser).is_ok());
+    assert!(Release::parse_with("\u{200f}myapp@1.0", &parser).is_err());
+    assert!(Release::parse("\u{200f}myapp@1.0").is_ok());
+
+    let sanitized = sanitize_release_with("my\u{200b}cafe\u{301}@1.0", &config());
+    assert_eq!(sanitized.value(), "mycaf\u{e9}@1.0");
+    assert_eq!(
+        sanitized.changes(),
+        [
+            SanitizeChange::RemovedCharacter {
+                character: '\u{200b}',
+                offset: 2
+            },
+            SanitizeChange::Normalized,
+        ]
+    );
+    assert_eq!(validate_release_with(sanitized.value(), &config()), Ok(()));
+}
@@ -0,0 +1,151 @@
+use sentry_release_parser::{
+    validate_environment, validate_environment_with, validate_release, validate_release_with,
+    CharacterPolicy, InvalidEnvironment, InvalidRelease, LengthUnit, Parser, Release,
+    ValidationConfig,
+};
+use similar_asserts::assert_eq;
+
+#[test]
+fn test_defaults() {
+    let release = ValidationConfig::release();
+    assert_eq!(release.max_length(), 200);
+    assert_eq!(release.length_unit(), LengthUnit::Bytes);
+    assert_eq!(
+        release.restricted_names().collect::<Vec<_>>(),
+        [".", "..", "latest"]
+    );
+    assert!(!release.is_case_sensitive());
+    assert_eq!(release.character_policy(), CharacterPolicy::Api);
+
+    for value in ["LATEST", "..", &"x".repeat(201), "foo/bar", "1.0"].iter() {
+        assert_eq!(
+            validate_release_with(value, &release),
+            validate_release(value)
+        );
+    }
+    for value in ["None", ".", &"x".repeat(65), "foo\tbar", "prod"].iter() {
+        assert_eq!(
+            validate_environment_with(value, &ValidationConfig::environment()),
+            validate_environment(value)
+        );
+    }
+}
+
+#[test]
+fn test_length() {
+    let config = ValidationConfig::environment().with_max_length(4);
+    assert_eq!(validate_environment_with("prod", &config), Ok(()));
+    assert_eq!(
+        validate_environment_with("prods", &config),
+        Err(InvalidEnvironment::TooLong {
+            length: 5,
+            limit: 4
+        })
+    );
+    assert_eq!(
+        validate_environment_with("pröd", &config),
+        Err(InvalidEnvironment::TooLong {
+            length: 5,
+            limit: 4
+        })
+    );
+
+    let config = config.with_length_unit(LengthUnit::Chars);
+    assert_eq!(config.length_of("pröd"), 4);
+    assert_eq!(validate_environment_with("pröd", &config), Ok(()));
+}
+
+#[test]
+fn test_restricted_names() {
+    let config = ValidationConfig::release().with_restricted_name("HEAD");
+    assert!(config.is_restricted("head"));
+    assert_eq!(
+        validate_release_with("head", &config),
+        Err(InvalidRelease::RestrictedName)
+    );
+
+    let config = config.with_case_sensitive(true);
+    assert_eq!(validate_release_with("head", &config), Ok(()));
+    assert_eq!(validate_release_with("Latest", &config), Ok(()));
+    assert_eq!(
+        validate_release_with("HEAD", &config),
+        Err(InvalidRelease::RestrictedName)
+    );
+
+    let config = ValidationConfig::release().without_restricted_names();
+    assert_eq!(validate_release_with("latest", &config), Ok(()));
+}
+
+#[test]
+fn test_character_policy() {
+    let config = ValidationConfig::release().with_character_policy(CharacterPolicy::PrintableAscii);
+    assert_eq!(validate_release_with("foo@1.0 beta", &config), Ok(()));
+    assert_eq!(
+        validate_release_with("foo@1.0-ß", &config),
+        Err(InvalidRelease::BadCharacters {
+            character: 'ß',
+            offset: 8
+        })
+    );
+    assert_eq!(
+        validate_release_with("foo/bar", &config),
+        Err(InvalidRelease::BadCharacters {
+            character: '/',
+            offset: 3
+        })
+    );
+    assert_eq!(validate_release("foo@1.0-ß"), Ok(()));
+}
+
+#[test]
+fn test_parser() {
+    let mut parser = Parser::new();
+    assert_eq!(parser.validation(), &ValidationConfig::release());
+    parser.set_validation(ValidationConfig::release().with_max_length(10));
+    assert_eq!(
+        Release::parse_with("foo@1.0.0-beta", &parser),
+        Err(InvalidRelease::TooLong {
+            length: 14,
+            limit: 10
+        })
+    );
+    assert!(Release::parse_with("foo@1.0.0", &parser).is_ok());
+    assert!(Release::parse("foo@1.0.0-beta").is_ok());
+}
+
+#[test]
+fn test_error_details() {
+    let err = validate_release("com.acme.app@1/2").unwrap_err();
+    assert_eq!(
+        err,
+        InvalidRelease::BadCharacters {
+            character: '/',
+            offset: 14
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid release: bad characters in release name"
+    );
+    assert_eq!(
+        format!("{:#}", err),
+        "invalid release: bad characters in release name ('/' at position 14)"
+    );
+
+    let err = validate_release(&"x".repeat(201)).unwrap_err();
+    assert_eq!(err.to_string(), "invalid release: release name too long");
+    assert_eq!(
+        format!("{:#}", err),
+        "invalid release: release name too long (length 201, limit 200)"
+    );
+
+    let err = validate_environment("prod\t1").unwrap_err();
+    assert_eq!(
+        format!("{:#}", err),
+        "invalid environment: bad characters in environment name ('\\t' at position 4)"
+    );
+    assert_eq!(
+        format!("{:#}", validate_environment("none").unwrap_err()),
+        "invalid environment: restricted environment name"
+    );
+}